@@ -0,0 +1,80 @@
+// benchmark for parser generating (time & peak memory of each phase) and for the generated parser (throughput)
+// run with `cargo run --release -p bench -- [filter] [--lr1]`, only grammars whose names contain `filter` are tested
+// canonical lr1 is slow on large grammars, so it is only run on small ones, unless `--lr1` is given
+mod synthetic;
+mod runtime;
+
+use common::*;
+use lalr1_core::*;
+use parser_gen::*;
+use re2dfa::re2dfa;
+use std::{alloc::{GlobalAlloc, Layout, System}, sync::atomic::{AtomicUsize, Ordering::Relaxed}, time::Instant};
+
+struct Counter;
+
+static CUR: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counter {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    let p = System.alloc(layout);
+    if !p.is_null() { PEAK.fetch_max(CUR.fetch_add(layout.size(), Relaxed) + layout.size(), Relaxed); }
+    p
+  }
+
+  unsafe fn dealloc(&self, p: *mut u8, layout: Layout) {
+    System.dealloc(p, layout);
+    CUR.fetch_sub(layout.size(), Relaxed);
+  }
+}
+
+#[global_allocator]
+static ALLOC: Counter = Counter;
+
+// run `f`, print its time and the peak memory allocated during it (memory allocated before it is not counted)
+fn measure<T>(grammar: &str, phase: &str, f: impl FnOnce() -> T) -> T {
+  let base = CUR.load(Relaxed);
+  PEAK.store(base, Relaxed);
+  let t = Instant::now();
+  let ret = f();
+  let secs = t.elapsed().as_secs_f64();
+  println!("{:<20}{:<16}{:>12.3}{:>14}", grammar, phase, secs * 1000.0, (PEAK.load(Relaxed) - base) / 1024);
+  ret
+}
+
+fn bench_grammar(name: &str, src: &str, run_lr1: bool) {
+  let mut raw = parse_lines(src).expect("invalid grammar");
+  let dfa = measure(name, "re2dfa", || re2dfa(raw.lexical.iter().map(|(s, _)| s.as_bytes()))).expect("invalid lexical");
+  let ref g = raw.extend(false).expect("invalid grammar");
+  measure(name, "ll1", || ll1_core::LLCtx::new(g));
+  let lr0 = measure(name, "lr0", || lr0::work(g));
+  let lr1 = measure(name, "lalr1_by_lr0", || lalr1_by_lr0::work(lr0, g));
+  if run_lr1 { measure(name, "lr1", || lr1::work(g)); }
+  let mut table = measure(name, "mk_table", || mk_table::mk_table(&lr1, g));
+  let (conflict, _) = measure(name, "solve", || mk_table::solve(&mut table, g));
+  let code_len = measure(name, "codegen", || {
+    let mut cfg = Config::new(Lang::Rs, Vec::new());
+    cfg.on_conflict = |_| {};
+    cfg.rs_lalr1(g, &table, &dfa).expect("failed to generate code");
+    cfg.code_output.len()
+  });
+  println!("{:<20}{} productions, {} lalr1 states, {} conflicts, {} bytes of code\n", name, g.prod.len(), lr1.len(), conflict.iter().filter(|c| c.resolved.is_none()).count(), code_len);
+}
+
+fn main() {
+  let args = std::env::args().skip(1).collect::<Vec<_>>();
+  let all_lr1 = args.iter().any(|x| x == "--lr1");
+  let filter = args.iter().find(|x| !x.starts_with("--")).map(|x| x.as_str()).unwrap_or("");
+  // (name, grammar, whether to run canonical lr1 by default)
+  let grammars = vec![
+    ("c", include_str!("../grammars/c.cfg").to_owned(), true),
+    ("expr_ladder_30", synthetic::expr_ladder(30), true),
+    ("java", include_str!("../grammars/java.cfg").to_owned(), false),
+    ("sql", include_str!("../grammars/sql.cfg").to_owned(), false),
+  ];
+  println!("{:<20}{:<16}{:>12}{:>14}", "grammar", "phase", "time(ms)", "peak(KB)");
+  for (name, src, run_lr1) in &grammars {
+    if name.contains(filter) { bench_grammar(name, src, *run_lr1 || all_lr1); }
+  }
+  if "generated_rs".contains(filter) { runtime::run(8 << 20); }
+}
@@ -0,0 +1,67 @@
+use parser_macros::lalr1;
+use std::time::Instant;
+
+pub struct Parser;
+
+#[lalr1(Expr)]
+#[lex = r#"
+priority = [
+  { assoc = 'left', terms = ['Add', 'Sub'] },
+  { assoc = 'left', terms = ['Mul'] },
+]
+
+[lexical]
+'\(' = 'LPar'
+'\)' = 'RPar'
+'\+' = 'Add'
+'-' = 'Sub'
+'\*' = 'Mul'
+'\d+' = 'IntLit'
+'\s+' = '_Eps'
+"#]
+impl Parser {
+  #[rule = "Expr -> Expr Add Expr"]
+  fn expr_add(l: u32, _op: Token, r: u32) -> u32 { l.wrapping_add(r) }
+  #[rule = "Expr -> Expr Sub Expr"]
+  fn expr_sub(l: u32, _op: Token, r: u32) -> u32 { l.wrapping_sub(r) }
+  #[rule = "Expr -> Expr Mul Expr"]
+  fn expr_mul(l: u32, _op: Token, r: u32) -> u32 { l.wrapping_mul(r) }
+  #[rule = "Expr -> LPar Expr RPar"]
+  fn expr_paren(_l: Token, i: u32, _r: Token) -> u32 { i }
+  #[rule = "Expr -> IntLit"]
+  fn expr_int(i: Token) -> u32 { i.piece.iter().fold(0u32, |acc, &ch| acc.wrapping_mul(10).wrapping_add((ch - b'0') as u32)) }
+}
+
+// an expression of about `size` bytes
+fn input(size: usize) -> Vec<u8> {
+  const PIECES: [&[u8]; 4] = [b"(12 + 345 * 6 - 7) * ", b"8 - (9 * (10 + 11)) + ", b"1234567 * 89 + ", b"(((1))) - "];
+  let mut s = Vec::with_capacity(size + 32);
+  let mut i = 0;
+  while s.len() < size {
+    s.extend_from_slice(PIECES[i % PIECES.len()]);
+    i += 1;
+  }
+  s.push(b'0');
+  s
+}
+
+fn report(phase: &str, bytes: usize, tokens: usize, t: Instant) {
+  let secs = t.elapsed().as_secs_f64();
+  println!("{:<20}{:<16}{:>12.3}{:>14.1}{:>14.1}", "generated_rs", phase, secs * 1000.0,
+    bytes as f64 / secs / (1 << 20) as f64, tokens as f64 / secs / 1e6);
+}
+
+// throughput of the generated rust lexer and parser
+pub fn run(size: usize) {
+  let input = input(size);
+  println!("{:<20}{:<16}{:>12}{:>14}{:>14}", "grammar", "phase", "time(ms)", "MB/s", "Mtoken/s");
+  let t = Instant::now();
+  let mut lexer = Lexer::new(&input);
+  let mut tokens = 0;
+  while lexer.next().kind != TokenKind::_Eof { tokens += 1; }
+  report("lex", input.len(), tokens, t);
+  let t = Instant::now();
+  let res = Parser.parse(&mut Lexer::new(&input));
+  assert!(res.is_ok(), "generated parser failed to parse benchmark input");
+  report("lex + parse", input.len(), tokens, t);
+}
@@ -0,0 +1,12 @@
+// generators of large grammars in the format of `common::parse_lines`
+
+// an expression grammar with `level` binary operator precedence levels, written as the classic non-terminal ladder
+pub fn expr_ladder(level: usize) -> String {
+  let mut s = String::new();
+  for i in 0..level {
+    s += &format!("E{0} -> E{0} Op{0}a E{1}\nE{0} -> E{0} Op{0}b E{1}\nE{0} -> E{1}\n", i, i + 1);
+  }
+  s += &format!("E{0} -> LPar E0 RPar\nE{0} -> Sub E{0}\nE{0} -> Id\nE{0} -> Id LPar Args RPar\n", level);
+  s += "Args -> E0\nArgs -> Args Comma E0\n";
+  s
+}
@@ -16,6 +16,10 @@ pub enum Assoc { Left, Right, NoAssoc }
 pub struct RawGrammar<'a> {
   pub include: &'a str,
   pub priority: Vec<RawPriorityRow<'a>>,
+  // resolutions for specific conflicts, tried before priority, see `RawResolve`
+  #[serde(default, borrow)] pub resolve: Vec<RawResolve<'a>>,
+  // ll1 only, non-terminals parsed by precedence climbing, see `Pratt`
+  #[serde(default, borrow)] pub pratt: Vec<&'a str>,
   // map re to term
   // K must be Cow<str>, because sometimes we have to write escape chars in the key string
   // so the key may not be a borrow from the input string
@@ -41,6 +45,8 @@ pub const ERR: &str = "_Err";
 pub const EPS_IDX: usize = 0;
 pub const EOF_IDX: usize = 1;
 pub const ERR_IDX: usize = 2;
+// productions can use `error` for the terminal `_Err` (unless `error` is defined by the user), for yacc-style error recovery in lalr1 mode
+pub const ERROR: &str = "error";
 
 #[derive(Deserialize)]
 pub struct RawPriorityRow<'a> {
@@ -49,10 +55,24 @@ pub struct RawPriorityRow<'a> {
   pub terms: Vec<&'a str>,
 }
 
+// a conflict on lookahead `token` is resolved by this directive if:
+// `prefer` = "shift": it is a shift-reduce conflict, the reduced production's lhs is `lhs` and it is `over` (if they are provided)
+// `prefer` = "reduce": it is a shift-reduce conflict, and the reduced production's lhs is `lhs` (if provided)
+// `prefer` = a production string like "A -> b C": it is a reduce-reduce conflict, one of the actions is reducing this production, and the other is reducing `over` (if provided)
+// the preferred action is chosen, and the other is removed; the first matching directive is used
+#[derive(Deserialize)]
+pub struct RawResolve<'a> {
+  pub token: &'a str,
+  pub lhs: Option<&'a str>,
+  pub prefer: &'a str,
+  pub over: Option<&'a str>,
+}
+
 #[derive(Deserialize)]
 pub struct RawProduction<'a> {
   pub lhs: &'a str,
-  pub ty: &'a str,
+  // can be omitted in cst mode, where it is ignored
+  #[serde(default)] pub ty: &'a str,
   pub rhs: Vec<RawProductionRhs<'a>>,
 }
 
@@ -63,7 +83,8 @@ pub struct RawProductionRhs<'a> {
   // it would not be pleasing if you provide it from toml config file(but you can, any way)
   // when it exists, it must have the same size as `rhs`, and each element is a (name, type) pair
   pub rhs_arg: Option<Vec<(&'a str, &'a str)>>,
-  pub act: &'a str,
+  // can be omitted in cst mode, where it is ignored
+  #[serde(default)] pub act: &'a str,
   pub prec: Option<&'a str>,
 }
 
@@ -120,6 +141,41 @@ pub struct Grammar<'a> {
   pub terms: Vec<Term<'a>>,
   pub nt: Vec<NonTerm<'a>>,
   pub prod: Vec<Prod<'a>>,
+  pub resolve: Vec<Resolve>,
+  pub pratt: Vec<Pratt>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum ResolveAct { Shift, Reduce, Prod(u32) }
+
+// all fields are ids: `token` is a terminal id, `lhs` is an index in `nt`, `over` and `ResolveAct::Prod` are production ids
+#[derive(Copy, Clone)]
+pub struct Resolve {
+  pub token: u32,
+  pub lhs: Option<u32>,
+  pub prefer: ResolveAct,
+  pub over: Option<u32>,
+}
+
+// an operator of a pratt non-terminal `E`, `prod` is "E -> op E" (prefix), "E -> E op E" (infix) or "E -> E op" (postfix)
+// `pri` and `assoc` come from the priority of the production (so `prec` can be used, e.g., for unary minus)
+#[derive(Copy, Clone)]
+pub struct PrattOp {
+  pub op: u32,
+  pub prod: u32,
+  pub pri: u32,
+  pub assoc: Assoc,
+}
+
+// a non-terminal parsed by precedence climbing in ll1 mode, its infix and postfix productions are not in the ll1 table
+// other productions of it (atoms) must not start with itself, the operand of a prefix operator is parsed with its priority
+pub struct Pratt {
+  // index in `nt`
+  pub nt: u32,
+  // sorted by `op`
+  pub prefix: Vec<PrattOp>,
+  pub infix: Vec<PrattOp>,
+  pub postfix: Vec<PrattOp>,
 }
 
 pub struct Term<'a> {
@@ -144,6 +200,10 @@ pub struct Prod<'a> {
   // index in prod
   pub id: u32,
   pub pri: Option<u32>,
+  // the terminal that decides `pri`: the `prec` term if provided, otherwise the last terminal in rhs
+  pub pri_term: Option<u32>,
+  // whether `pri_term` comes from `prec`
+  pub by_prec: bool,
 }
 
 impl RawGrammar<'_> {
@@ -192,20 +252,21 @@ impl RawGrammar<'_> {
     nt.last_mut().unwrap().ty = nt[*nt2id.get(self.start).ok_or_else(||
       format!("start non-term \"{}\" undefined", self.start))? as usize].ty;
 
+    let term_id = |name: &str| term2id.get(name).copied().or_else(|| if name == ERROR { Some(ERR_IDX as u32) } else { None });
     let mut prod = vec![Vec::new(); nt.len()];
     for raw_prod in &self.production {
       let lhs = nt2id[raw_prod.lhs];
       let lhs_prod = &mut prod[lhs as usize];
       for rhs in &raw_prod.rhs {
         let mut prod_rhs = ProdVec::new();
-        let mut prod_pri = None;
+        let mut pri_term = None;
         for rhs in &rhs.rhs {
           // impossible to have a (Some(), Some()) here, because we have checked that term & non-term don't have any duplicate name
-          match (nt2id.get(rhs), term2id.get(rhs)) {
+          match (nt2id.get(rhs), term_id(rhs)) {
             (Some(&nt), _) => prod_rhs.push(nt + terms.len() as u32),
-            (_, Some(&t)) => {
+            (_, Some(t)) => {
               prod_rhs.push(t);
-              prod_pri = terms[t as usize].pri_assoc.map(|(pri, _)| pri);
+              pri_term = Some(t);
             }
             _ => return Err(format!("production rhs contains undefined token: \"{}\"", rhs)),
           }
@@ -213,10 +274,11 @@ impl RawGrammar<'_> {
         if let Some(prec) = rhs.prec.as_ref() {
           match term2id.get(prec) {
             None => return Err(format!("prec uses undefined term: \"{}\"", prec)),
-            Some(&t) => prod_pri = terms[t as usize].pri_assoc.map(|(pri, _)| pri),
+            Some(&t) => pri_term = Some(t),
           }
         }
-        lhs_prod.push(Prod { rhs: prod_rhs, act: &rhs.act, args: rhs.rhs_arg.as_ref(), lhs, id: 0, pri: prod_pri });
+        let pri = pri_term.and_then(|t| terms[t as usize].pri_assoc).map(|(pri, _)| pri);
+        lhs_prod.push(Prod { rhs: prod_rhs, act: &rhs.act, args: rhs.rhs_arg.as_ref(), lhs, id: 0, pri, pri_term, by_prec: rhs.prec.is_some() });
 
         // type checking
         if let Some(rhs_arg) = &rhs.rhs_arg {
@@ -225,7 +287,7 @@ impl RawGrammar<'_> {
               raw_prod.lhs, rhs.rhs.join(" "), rhs.rhs.len(), rhs_arg.len()));
           }
           for (&rhs_tk, &(_, rhs_ty)) in rhs.rhs.iter().zip(rhs_arg.iter()) {
-            match (nt2id.get(rhs_tk), term2id.get(rhs_tk)) {
+            match (nt2id.get(rhs_tk), term_id(rhs_tk)) {
               (Some(&nt_id), _) => {
                 let nt_ty = nt[nt_id as usize].ty;
                 if nt_ty != rhs_ty {
@@ -250,7 +312,61 @@ impl RawGrammar<'_> {
     }
     let mut prod = prod.into_iter().flat_map(|x| x.into_iter()).collect::<Vec<_>>();
     for (idx, prod) in prod.iter_mut().enumerate() { prod.id = idx as u32; }
-    Ok(Grammar { raw: self, nt, terms, prod })
+
+    // find a production by a string like "A -> b C"
+    let find_prod = |s: &str| -> Result<u32, String> {
+      let (lhs, rhs) = parse_arrow_prod(s).ok_or_else(|| format!("resolve uses invalid production: \"{}\"", s))?;
+      let lhs = *nt2id.get(lhs).ok_or_else(|| format!("resolve uses undefined non-term: \"{}\"", lhs))? as usize;
+      let rhs = rhs.iter().map(|x| nt2id.get(x).map(|&x| x + terms.len() as u32).or_else(|| term_id(x)))
+        .collect::<Option<ProdVec>>().ok_or_else(|| format!("resolve uses production with undefined token: \"{}\"", s))?;
+      let end = nt.get(lhs + 1).map(|x| x.start_idx).unwrap_or(prod.len());
+      prod[nt[lhs].start_idx..end].iter().find(|p| p.rhs == rhs).map(|p| p.id)
+        .ok_or_else(|| format!("resolve uses undefined production: \"{}\"", s))
+    };
+    let mut resolve = Vec::with_capacity(self.resolve.len());
+    for r in &self.resolve {
+      let token = *term2id.get(r.token).ok_or_else(|| format!("resolve uses undefined term: \"{}\"", r.token))?;
+      let lhs = match r.lhs {
+        Some(lhs) => Some(*nt2id.get(lhs).ok_or_else(|| format!("resolve uses undefined non-term: \"{}\"", lhs))?),
+        None => None,
+      };
+      let prefer = match r.prefer {
+        "shift" => ResolveAct::Shift,
+        "reduce" => ResolveAct::Reduce,
+        p => ResolveAct::Prod(find_prod(p)?),
+      };
+      let over = match r.over {
+        Some(_) if prefer == ResolveAct::Reduce => return Err(format!("resolve on term \"{}\" prefers reduce, it can't have `over`", r.token)),
+        Some(over) => Some(find_prod(over)?),
+        None => None,
+      };
+      resolve.push(Resolve { token, lhs, prefer, over });
+    }
+    let mut pratt = Vec::with_capacity(self.pratt.len());
+    for &name in &self.pratt {
+      let lhs = *nt2id.get(name).ok_or_else(|| format!("pratt uses undefined non-term: \"{}\"", name))?;
+      let (term_num, e) = (terms.len() as u32, lhs + terms.len() as u32);
+      let (mut prefix, mut infix, mut postfix) = (Vec::new(), Vec::new(), Vec::new());
+      let end = nt.get(lhs as usize + 1).map(|x| x.start_idx).unwrap_or(prod.len());
+      for p in &prod[nt[lhs as usize].start_idx..end] {
+        let (ops, op) = match p.rhs.as_slice() {
+          &[op, x] if x == e && op < term_num => (&mut prefix, op),
+          &[x, op, y] if x == e && y == e && op < term_num => (&mut infix, op),
+          &[x, op] if x == e && op < term_num => (&mut postfix, op),
+          &[x, ..] if x == e => return Err(format!("production of pratt non-term \"{}\" starts with itself, but it is not an infix or postfix operator", name)),
+          _ => continue,
+        };
+        let (pri, assoc) = p.pri_term.and_then(|t| terms[t as usize].pri_assoc)
+          .ok_or_else(|| format!("operator \"{}\" of pratt non-term \"{}\" has no priority", terms[op as usize].name, name))?;
+        ops.push(PrattOp { op, prod: p.id, pri, assoc });
+      }
+      for ops in [&mut prefix, &mut infix, &mut postfix] { ops.sort_unstable_by_key(|x| x.op); }
+      if let Some(x) = infix.iter().find(|x| postfix.binary_search_by_key(&x.op, |y| y.op).is_ok()) {
+        return Err(format!("operator \"{}\" of pratt non-term \"{}\" is both infix and postfix", terms[x.op as usize].name, name));
+      }
+      pratt.push(Pratt { nt: lhs, prefix, infix, postfix });
+    }
+    Ok(Grammar { raw: self, nt, terms, prod, resolve, pratt })
   }
 }
 
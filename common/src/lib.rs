@@ -17,4 +17,32 @@ pub fn parse_arrow_prod(s: &str) -> Option<(&str, Vec<&str>)> {
   let lhs = sp.next()?;
   match sp.next() { Some("->") => {} _ => return None };
   Some((lhs, sp.collect()))
+}
+
+// parse a grammar with one "lhs -> rhs1 rhs2 ..." production per line, the first lhs is the start non-term
+// all tokens that don't appear as lhs are terminals, their regexes are their indices (they are not expected to be lexed)
+pub fn parse_lines(s: &str) -> Result<RawGrammar, String> {
+  let mut production = Vec::new();
+  let mut all_lhs = HashSet::default();
+  for s in s.lines() {
+    let (lhs, rhs) = parse_arrow_prod(s).ok_or_else(|| format!("invalid input \"{}\", expect form of \"lhs -> rhs1 rhs2 ...\"", s))?;
+    if lhs == START_NT_NAME || rhs.iter().any(|&x| x == START_NT_NAME) {
+      // we are not going to validate user input names (see `raw.extend(false)`)
+      // so we should check it here manually that the manually added START_NT_NAME isn't used
+      return Err(format!("invalid token name: \"{}\"", START_NT_NAME));
+    }
+    all_lhs.insert(lhs);
+    production.push(RawProduction { lhs, ty: "", rhs: vec![RawProductionRhs { rhs, rhs_arg: None, act: "", prec: None }] });
+  }
+  let start = production.get(0).ok_or_else(|| "grammar must have at least one production rule".to_owned())?.lhs;
+  let mut lexical = IndexMap::default();
+  for p in &production {
+    for r in &p.rhs {
+      for &r in &r.rhs {
+        // use current len as terminal regex
+        if !all_lhs.contains(r) { lexical.insert(lexical.len().to_string().into(), r); }
+      }
+    }
+  }
+  Ok(RawGrammar { include: "", priority: vec![], resolve: vec![], pratt: vec![], lexical, lexer_field: "", lexer_action: "", parser_field: "", start, production, parser_def: None })
 }
\ No newline at end of file
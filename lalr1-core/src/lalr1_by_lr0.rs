@@ -17,7 +17,7 @@ pub fn work<'a>(lr0: Lr0Fsm<'a>, g: &'a Grammar<'a>) -> Lr1Fsm<'a> {
     }
   }
 
-  // use ERR_IDX as the special token
+  // use EPS_IDX as the special token, it is never a real lookahead (while ERR_IDX is, as the `error` token)
   for (i, Lr0Node { closure: state, link }) in lr0.iter().enumerate() {
     for (item_id, &item) in state.iter().enumerate() {
       // only consider lr0 core item
@@ -25,7 +25,7 @@ pub fn work<'a>(lr0: Lr0Fsm<'a>, g: &'a Grammar<'a>) -> Lr1Fsm<'a> {
         unsafe {
           let cl = ctx.closure({
             let lookahead = bitset::bsmake(g.token_num());
-            bitset::ubs(lookahead.as_ref()).set(ERR_IDX);
+            bitset::ubs(lookahead.as_ref()).set(EPS_IDX);
             let mut init = HashMap::default();
             init.insert(item, lookahead);
             init
@@ -33,13 +33,14 @@ pub fn work<'a>(lr0: Lr0Fsm<'a>, g: &'a Grammar<'a>) -> Lr1Fsm<'a> {
           let from = lookahead.get_unchecked(i).as_ptr().add(item_id * elem_len);
           for Lr1Item { lr0: cl_item, lookahead: cl_lookahead } in &cl {
             if let Some(ch) = cl_item.prod.get(cl_item.dot as usize) {
-              let goto_state = *link.get(ch).unwrap_or_else(|| std::hint::unreachable_unchecked()) as usize;
+              let goto_state = *sorted_get(link, *ch).unwrap_or_else(|| std::hint::unreachable_unchecked()) as usize;
               let goto_item_id = cl_item.unique_id() + 1; // dot + 1
-              let goto_item_idx = lr0.get_unchecked(goto_state).closure.iter()
-                .position(|item| item.unique_id() == goto_item_id).unwrap_or_else(|| std::hint::unreachable_unchecked());
+              // closure is sorted by unique_id
+              let goto_item_idx = lr0.get_unchecked(goto_state).closure
+                .binary_search_by_key(&goto_item_id, |item| item.unique_id()).unwrap_or_else(|_| std::hint::unreachable_unchecked());
               let goto_lookahead = lookahead.get_unchecked(goto_state).as_ptr().add(goto_item_idx * elem_len);
               bitset::ubs1(goto_lookahead).or(cl_lookahead.as_ptr(), elem_len);
-              if bitset::ubs(cl_lookahead.as_ref()).get(ERR_IDX) {
+              if bitset::ubs(cl_lookahead.as_ref()).get(EPS_IDX) {
                 prop.push((from, goto_lookahead));
               }
             }
@@ -64,11 +65,90 @@ pub fn work<'a>(lr0: Lr0Fsm<'a>, g: &'a Grammar<'a>) -> Lr1Fsm<'a> {
       let l = lookahead.get_unchecked(i).as_ptr();
       for (i, closure) in closure.into_iter().enumerate() {
         let l = l.add(i * elem_len);
-        bitset::ubs1(l).del(ERR_IDX);
+        bitset::ubs1(l).del(EPS_IDX);
         lr1_closure.insert(closure, Box::from(std::slice::from_raw_parts(l, elem_len)));
       }
     }
     result.push(Lr1Node { closure: ctx.closure(lr1_closure, g), link });
   }
   result
-}
\ No newline at end of file
+}
+
+// how an item gets a lookahead token, see `explain`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LookaheadReason {
+  // `_Eof` is the initial lookahead of the start item
+  Init,
+  // the item `B -> . gamma` is generated by the closure of item `from` `A -> alpha . B beta` in the same state,
+  // and the token is in FIRST(beta)
+  Spontaneous { from: u32 },
+  // propagated from the previous item `A -> alpha . B beta` by closure, because beta is nullable
+  Closure,
+  // propagated from the previous item `A -> alpha . X beta` by goto on X
+  Goto,
+}
+
+// (state, item index in the state's closure, how the item gets the token)
+pub type ExplainStep = (u32, u32, LookaheadReason);
+
+// explain why `token` is in the lookahead of `lr1[state].closure[item]`, `lr1` can be the result of `work` or `lr1::work`
+// the result is a shortest chain of items, starting from where the token is generated, and ending with the asked item
+// return None if the token is not in the lookahead
+pub fn explain(lr1: &Lr1Fsm, g: &Grammar, state: u32, item: u32, token: u32) -> Option<Vec<ExplainStep>> {
+  let has = |s: u32, i: u32| bitset::ibs(&lr1[s as usize].closure[i as usize].lookahead).get(token as usize);
+  if lr1.get(state as usize)?.closure.get(item as usize).is_none() || token as usize >= g.terms.len() || !has(state, item) { return None; }
+  let first = ll1_core::First::new(g);
+  let start_prod = g.start().1.id;
+  let mut pred = vec![Vec::new(); lr1.len()];
+  for (p, s) in lr1.iter().enumerate() {
+    for &(ch, q) in &s.link { pred[q as usize].push((p as u32, ch)); }
+  }
+  // searching backward from the asked item, `next` maps an item to the item after it in the chain, and the reason of that item
+  let mut next = HashMap::<_, Option<((u32, u32), LookaheadReason)>>::default();
+  next.insert((state, item), None);
+  let mut q = VecDeque::new();
+  q.push_back((state, item));
+  while let Some((s, i)) = q.pop_front() {
+    let it = &lr1[s as usize].closure[i as usize];
+    let mut origin = None;
+    let mut prev = Vec::new();
+    if it.prod_id == start_prod && it.dot == 0 {
+      if token as usize == EOF_IDX { origin = Some(LookaheadReason::Init); }
+    } else if it.dot == 0 {
+      let lhs = g.prod[it.prod_id as usize].lhs + g.terms.len() as u32;
+      for (j, parent) in lr1[s as usize].closure.iter().enumerate() {
+        if parent.prod.get(parent.dot as usize) != Some(&lhs) { continue; }
+        let beta_first = first.first(&parent.prod[parent.dot as usize + 1..], g);
+        if bitset::ibs(&beta_first).get(token as usize) {
+          origin = Some(LookaheadReason::Spontaneous { from: j as u32 });
+          break;
+        }
+        if bitset::ibs(&beta_first).get(EPS_IDX) && has(s, j as u32) { prev.push(((s, j as u32), LookaheadReason::Closure)); }
+      }
+    } else {
+      let (x, prev_id) = (it.prod[it.dot as usize - 1], it.unique_id() - 1); // dot - 1
+      for &(p, ch) in &pred[s as usize] {
+        if ch != x { continue; }
+        if let Ok(j) = lr1[p as usize].closure.binary_search_by_key(&prev_id, |item| item.unique_id()) {
+          if has(p, j as u32) { prev.push(((p, j as u32), LookaheadReason::Goto)); }
+        }
+      }
+    }
+    if let Some(origin) = origin {
+      let mut chain = vec![(s, i, origin)];
+      let mut cur = (s, i);
+      while let Some(&Some((n, r))) = next.get(&cur) {
+        chain.push((n.0, n.1, r));
+        cur = n;
+      }
+      return Some(chain);
+    }
+    for (node, r) in prev {
+      if !next.contains_key(&node) {
+        next.insert(node, Some(((s, i), r)));
+        q.push_back(node);
+      }
+    }
+  }
+  None
+}
@@ -28,9 +28,12 @@ pub struct Lr1Item<'a> {
 pub type Lr0Closure<'a> = Vec<Lr0Item<'a>>;
 pub type Lr1Closure<'a> = Vec<Lr1Item<'a>>;
 
+// (token, target state), sorted by token
+pub type Link = Vec<(u32, u32)>;
+
 pub struct Lr0Node<'a> {
   pub closure: Lr0Closure<'a>,
-  pub link: HashMap<u32, u32>,
+  pub link: Link,
 }
 
 // originally the `link` field type is a generic parameter L: Borrow<Link>
@@ -39,7 +42,7 @@ pub struct Lr0Node<'a> {
 // although it can indeed eliminate the unnecessary clone
 pub struct Lr1Node<'a> {
   pub closure: Lr1Closure<'a>,
-  pub link: HashMap<u32, u32>,
+  pub link: Link,
 }
 
 pub type Lr0Fsm<'a> = Vec<Lr0Node<'a>>;
@@ -55,8 +58,10 @@ pub struct TableEntry<'a> {
   // actually we are now only using the Lr0Closure part of it, but these Lr1Closure can't be directly converted Lr0Closure
   // so now just leave it as Lr1Closure; if we need Lr0Closure in the future, we can write a trait to extract the common behaviour of them
   pub closure: &'a Lr1Closure<'a>,
-  pub act: HashMap<u32, Acts>,
-  pub goto: HashMap<u32, u32>,
+  // (terminal, actions), sorted by terminal
+  pub act: Vec<(u32, Acts)>,
+  // (non-terminal, target state), sorted by non-terminal
+  pub goto: Vec<(u32, u32)>,
 }
 
 pub type Table<'a> = Vec<TableEntry<'a>>;
@@ -67,16 +72,43 @@ pub enum ConflictKind {
   Many(Acts),
 }
 
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum Resolved {
+  // by the directive `g.resolve[i]`, such conflicts don't need to be reported
+  Directive(u32),
+  // by precedence and associativity, only recorded for conflicts of more than 2 actions (`ConflictKind::Many`)
+  // true if one action is kept, which is placed first in `Many`, false if all are removed (by nonassoc)
+  Priority(bool),
+}
+
 pub struct Conflict {
   pub kind: ConflictKind,
   pub state: u32,
   pub ch: u32,
+  // None if more than one action remain
+  pub resolved: Option<Resolved>,
+  // Some if it is a reduce-reduce conflict of lalr1 that doesn't exist in canonical lr1 (set by `mark_lalr_only`)
+  // each element is (canonical lr1 state merged into this state, the production it reduces on `ch`)
+  pub lalr_only: Option<Vec<(u32, u32)>>,
+}
+
+// which priority declarations decided at least one conflict in `solve`
+pub struct PriUsed {
+  // indexed by terminal id, whether the terminal's priority (from `RawPriorityRow`) is used
+  pub term: Vec<bool>,
+  // indexed by production id, whether the production's `prec` is used
+  pub prec: Vec<bool>,
 }
 
 impl Conflict {
   pub fn is_many(&self) -> bool { match self.kind { ConflictKind::Many(_) => true, _ => false } }
 }
 
+// look up `ch` in a list sorted by token, like `Link`, `TableEntry::act` and `TableEntry::goto`
+pub fn sorted_get<T>(l: &[(u32, T)], ch: u32) -> Option<&T> {
+  l.binary_search_by_key(&ch, |x| x.0).ok().map(|i| &l[i].1)
+}
+
 impl Lr0Item<'_> {
   pub fn unique_id(&self) -> u64 { ((self.prod_id as u64) << 32) | (self.dot as u64) }
 }
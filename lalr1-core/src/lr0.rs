@@ -1,57 +1,63 @@
 use crate::*;
 
-fn go<'a>(items: &Vec<Lr0Item<'a>>, mov: u32, g: &'a Grammar<'a>) -> Vec<Lr0Item<'a>> {
-  let mut new_items = HashSet::default();
-  for &Lr0Item { prod, prod_id, dot } in items {
-    if prod.get(dot as usize) == Some(&mov) {
-      new_items.insert(Lr0Item { prod, prod_id, dot: dot + 1 });
-    }
-  }
-  closure(new_items, g)
-}
-
-fn closure<'a>(mut items: HashSet<Lr0Item<'a>>, g: &'a Grammar<'a>) -> Vec<Lr0Item<'a>> {
-  let mut q = items.clone().into_iter().collect::<VecDeque<_>>();
-  while let Some(item) = q.pop_front() {
+// the closure of `kernel`, a non-terminal's productions are added only once, so no set of items is needed
+fn closure<'a>(kernel: &[Lr0Item<'a>], g: &'a Grammar<'a>) -> Vec<Lr0Item<'a>> {
+  let mut vis = vec![false; g.nt.len()];
+  let mut items = kernel.to_vec();
+  let mut i = 0;
+  while let Some(&item) = items.get(i) {
+    i += 1;
     // if the token after dot is a non-terminal
-    if let Some(ch) = item.prod.get(item.dot as usize).and_then(|&ch| g.as_nt(ch)) { // if the token after dot is a non-terminal
-      for new_prod in g.get_prod(ch) {
-        let new_item = Lr0Item { prod: &new_prod.rhs, prod_id: new_prod.id, dot: 0 };
-        if items.insert(new_item) {
-          q.push_back(new_item);
-        }
+    if let Some(ch) = item.prod.get(item.dot as usize).and_then(|&ch| g.as_nt(ch)) {
+      if !vis[ch] {
+        vis[ch] = true;
+        items.extend(g.get_prod(ch).iter().map(|p| Lr0Item { prod: &p.rhs, prod_id: p.id, dot: 0 }));
       }
     }
   }
-  let mut items = items.into_iter().collect::<Vec<_>>();
   items.sort_unstable();
+  items.dedup();
   items
 }
 
 pub fn work<'a>(g: &'a Grammar) -> Lr0Fsm<'a> {
-  let token_num = g.token_num() as u32;
+  // a state is identified by its kernel, so closure is only computed for new states
   let mut ss = HashMap::default();
-  let init = closure({
+  let init = {
     let start = g.start().1;
-    let mut init = HashSet::default();
-    init.insert(Lr0Item { prod: &start.rhs, prod_id: start.id, dot: 0 });
-    init
-  }, g);
-  ss.insert(init.clone(), 0);
-  let mut q = VecDeque::new();
-  let mut result = Vec::new();
-  q.push_back(init);
-  while let Some(cur) = q.pop_front() {
-    let mut link = HashMap::default();
-    for mov in 0..token_num {
-      let ns = go(&cur, mov, g);
-      if !ns.is_empty() {
-        let new_id = ss.len() as u32;
-        let id = *ss.entry(ns.clone()).or_insert_with(|| (q.push_back(ns), new_id).1);
-        link.insert(mov, id);
-      }
+    vec![Lr0Item { prod: &start.rhs, prod_id: start.id, dot: 0 }]
+  };
+  let mut result = vec![Lr0Node { closure: closure(&init, g), link: Vec::new() }];
+  ss.insert(init, 0);
+  // (token after dot, item with dot moved forward)
+  let mut next = Vec::new();
+  let mut i = 0;
+  // states are numbered in bfs order, the same as the order of being pushed to `result`
+  while i < result.len() {
+    next.clear();
+    for &Lr0Item { prod, prod_id, dot } in &result[i].closure {
+      if let Some(&ch) = prod.get(dot as usize) { next.push((ch, Lr0Item { prod, prod_id, dot: dot + 1 })); }
+    }
+    next.sort_unstable_by_key(|(ch, item)| (*ch, item.unique_id()));
+    let mut link = Vec::new();
+    let mut j = 0;
+    while j < next.len() {
+      let ch = next[j].0;
+      let kernel = next[j..].iter().take_while(|x| x.0 == ch).map(|x| x.1).collect::<Vec<_>>();
+      j += kernel.len();
+      let id = match ss.get(&kernel) {
+        Some(&id) => id,
+        None => {
+          let id = result.len() as u32;
+          result.push(Lr0Node { closure: closure(&kernel, g), link: Vec::new() });
+          ss.insert(kernel, id);
+          id
+        }
+      };
+      link.push((ch, id));
     }
-    result.push(Lr0Node { closure: cur, link });
+    result[i].link = link;
+    i += 1;
   }
   result
-}
\ No newline at end of file
+}
@@ -17,7 +17,7 @@ impl Lr1Ctx {
     beta_first
   }
 
-  // `go` was used by lr1 before, now not used
+  // `go` was used by lr1 before, now `work` builds all transitions of a state at once by grouping items
   pub fn go<'a>(&mut self, state: &Lr1Closure<'a>, mov: u32, g: &'a Grammar<'a>) -> Lr1Closure<'a> {
     let mut new_items = HashMap::default();
     for Lr1Item { lr0, lookahead } in state {
@@ -74,21 +74,29 @@ pub fn work<'a>(g: &'a Grammar) -> crate::Lr1Fsm<'a> {
     init.insert(item, lookahead);
     init
   }, g);
-  let mut q = VecDeque::new();
-  let mut result = Vec::new();
-  ss.insert(init.clone(), 0);
-  q.push_back(init);
-  while let Some(cur) = q.pop_front() {
-    let mut link = HashMap::default();
-    for mov in 0..g.token_num() as u32 {
-      let ns = ctx.go(&cur, mov, g);
-      if !ns.is_empty() {
-        let new_id = ss.len() as u32;
-        let id = *ss.entry(ns.clone()).or_insert_with(|| (q.push_back(ns), new_id).1);
-        link.insert(mov, id);
-      }
+  let mut result = vec![crate::Lr1Node { closure: init.clone(), link: Vec::new() }];
+  ss.insert(init, 0);
+  let mut i = 0;
+  while i < result.len() {
+    // group items by the token after dot, each group forms the kernel of a target state
+    let mut next = result[i].closure.iter()
+      .filter_map(|Lr1Item { lr0, lookahead }| lr0.prod.get(lr0.dot as usize).map(|&ch| (ch, Lr0Item { dot: lr0.dot + 1, ..*lr0 }, lookahead.clone())))
+      .collect::<Vec<_>>();
+    next.sort_by_key(|x| x.0);
+    let mut link = Vec::new();
+    let mut j = 0;
+    while j < next.len() {
+      let ch = next[j].0;
+      let kernel = next[j..].iter().take_while(|x| x.0 == ch)
+        .map(|(_, item, lookahead)| (*item, lookahead.clone())).collect::<HashMap<_, _>>();
+      j += kernel.len();
+      let ns = ctx.closure(kernel, g);
+      let new_id = result.len() as u32;
+      let id = *ss.entry(ns.clone()).or_insert_with(|| (result.push(crate::Lr1Node { closure: ns, link: Vec::new() }), new_id).1);
+      link.push((ch, id));
     }
-    result.push(crate::Lr1Node { closure: cur, link });
+    result[i].link = link;
+    i += 1;
   }
   result
-}
\ No newline at end of file
+}
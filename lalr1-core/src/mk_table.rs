@@ -4,22 +4,24 @@ pub fn mk_table<'a>(lr1: &'a Lr1Fsm<'a>, g: &'a Grammar<'a>) -> Table<'a> {
   let mut table = Vec::with_capacity(lr1.len());
   let start_id = g.start().1.id;
   for Lr1Node { closure, link } in lr1 {
-    let (mut act, mut goto) = (HashMap::default(), HashMap::default());
-    for (&k, &v) in link {
-      if g.as_nt(k).is_some() { goto.insert(k, v); } else { act.insert(k, smallvec![Act::Shift(v)]); }
-    }
+    // terminals are before non-terminals, and `link` is sorted, so the shifts and gotos are separated at one point
+    let split = link.iter().position(|&(k, _)| g.as_nt(k).is_some()).unwrap_or(link.len());
+    let goto = link[split..].to_vec();
+    let mut acts = vec![Acts::new(); g.terms.len()];
+    for &(k, v) in &link[..split] { acts[k as usize].push(Act::Shift(v)); }
     for Lr1Item { lr0, lookahead } in closure {
       if lr0.dot == lr0.prod.len() as u32 {
         if bitset::ibs(lookahead).get(EOF_IDX) && lr0.prod_id == start_id {
-          act.insert(EOF_IDX as u32, smallvec![Act::Acc]);
+          acts[EOF_IDX] = smallvec![Act::Acc];
         } else {
           bitset::ibs(lookahead).ones(|i| {
             // maybe conflict here
-            act.entry(i as u32).or_insert_with(SmallVec::new).push(Act::Reduce(lr0.prod_id));
+            acts[i].push(Act::Reduce(lr0.prod_id));
           });
         }
       }
     }
+    let act = acts.into_iter().enumerate().filter(|(_, x)| !x.is_empty()).map(|(i, x)| (i as u32, x)).collect();
     table.push(TableEntry { closure, act, goto });
   }
   table
@@ -40,46 +42,137 @@ pub fn mk_table<'a>(lr1: &'a Lr1Fsm<'a>, g: &'a Grammar<'a>) -> Table<'a> {
 // If precedences are equal, then associativity is used.
 // Left associative implies reduce; right associative implies shift; nonassociating implies error.
 
-// `solve` will modify t in these ways:
-// for conflicts solved based on precedence and/or associativity, other choices are removed
-// for conflicts solved based on location or "shift better than reduced", other choices are NOT removed
-// in both cases, the selected choice is placed at [0]
-pub fn solve<'a>(t: &mut Table<'a>, g: &'a Grammar<'a>) -> Vec<Conflict> {
+// find the first directive in `g.resolve` that can resolve the conflict of `a0` and `a1` on `ch`
+// return (index of the directive, the preferred action)
+fn find_resolve(g: &Grammar, ch: u32, a0: Act, a1: Act) -> Option<(u32, Act)> {
   use Act::{Reduce, Shift};
+  g.resolve.iter().enumerate().filter(|(_, r)| r.token == ch).find_map(|(i, r)| {
+    let lhs_ok = |p: u32| r.lhs.map_or(true, |lhs| g.prod[p as usize].lhs == lhs);
+    let over_ok = |a: Act| r.over.map_or(true, |over| a == Reduce(over));
+    // whether `x` is preferred over `y` by this directive
+    let prefer = |x: Act, y: Act| match (r.prefer, x, y) {
+      (ResolveAct::Shift, Shift(_), Reduce(p)) => lhs_ok(p) && over_ok(y),
+      (ResolveAct::Reduce, Reduce(p), Shift(_)) => lhs_ok(p),
+      (ResolveAct::Prod(p), Reduce(p1), Reduce(_)) => p == p1 && lhs_ok(p) && over_ok(y),
+      _ => false,
+    };
+    if prefer(a0, a1) { Some((i as u32, a0)) } else if prefer(a1, a0) { Some((i as u32, a1)) } else { None }
+  })
+}
+
+// decide which of `x` and `y` should be removed by resolve directives, or by precedence and associativity
+// return (remove x, remove y, index of the directive used), or None if they can't decide
+// the priority declarations that decide are marked in `used`
+fn decide(g: &Grammar, ch: u32, x: Act, y: Act, used: &mut PriUsed) -> Option<(bool, bool, Option<u32>)> {
+  use Act::{Acc, Reduce, Shift};
+  if let Some((i, a)) = find_resolve(g, ch, x, y) { return Some((a != x, a != y, Some(i))); }
+  let mut use_prod = |r: u32| {
+    let p = &g.prod[r as usize];
+    if let Some(t) = p.pri_term { used.term[t as usize] = true; }
+    used.prec[r as usize] |= p.by_prec;
+  };
+  match (x, y) {
+    (Reduce(r1), Reduce(r2)) => match (g.prod[r1 as usize].pri, g.prod[r2 as usize].pri) {
+      (Some(p1), Some(p2)) if p1 != p2 => {
+        use_prod(r1);
+        use_prod(r2);
+        Some((p1 < p2, p2 < p1, None))
+      }
+      _ => None,
+    },
+    (Reduce(r), Shift(_)) | (Shift(_), Reduce(r)) => {
+      let (pp, (cp, ca)) = (g.prod[r as usize].pri?, g.terms[ch as usize].pri_assoc?);
+      use_prod(r);
+      used.term[ch as usize] = true;
+      let (rm_shift, rm_reduce) = match pp.cmp(&cp) {
+        Less => (false, true), Greater => (true, false),
+        Equal => match ca { Assoc::Left => (true, false), Assoc::Right => (false, true), Assoc::NoAssoc => (true, true) }
+      };
+      Some(if let Shift(_) = x { (rm_shift, rm_reduce, None) } else { (rm_reduce, rm_shift, None) })
+    }
+    // accepting only happens on EOF, which is never shifted, so it can only conflict with reducing, which is never decided
+    (Acc, _) | (_, Acc) => None,
+    (Shift(_), Shift(_)) => unreachable!("there should be a bug in lr"),
+  }
+}
+
+// `acts` should be in the default order
+fn conflict_kind(acts: &[Act]) -> ConflictKind {
+  match *acts {
+    [Act::Shift(s), Act::Reduce(r)] => ConflictKind::SR { s, r },
+    [Act::Reduce(r1), Act::Reduce(r2)] => ConflictKind::RR { r1, r2 },
+    _ => ConflictKind::Many(acts.into()),
+  }
+}
+
+// `solve` will modify t in these ways:
+// every pair of actions in a conflict is compared by resolve directives, or by precedence and associativity (for shift-reduce)
+// or precedence (for reduce-reduce), and the losers are removed (nonassoc removes both, then the entry may become error)
+// if more than one action remain, the conflict is reported, and they are NOT removed
+// remaining actions are in the default order: shift first, then reduce by the earlier production in the grammar
+// in all cases, the selected choice is placed at [0]
+// conflicts solved by resolve directives are also returned, with `resolved` set, so that they can be shown
+// so are conflicts of more than 2 actions solved by precedence and associativity, with the kept action (if any) placed first
+// the priority declarations that decide at least one conflict are also returned
+pub fn solve<'a>(t: &mut Table<'a>, g: &'a Grammar<'a>) -> (Vec<Conflict>, PriUsed) {
+  use Act::{Acc, Reduce, Shift};
+  let default_order = |a: &Act| match *a { Acc | Shift(_) => 0, Reduce(r) => 1 + r as u64 };
   let mut reports = Vec::new();
+  let mut used = PriUsed { term: vec![false; g.terms.len()], prec: vec![false; g.prod.len()] };
   for (idx, t) in t.iter_mut().enumerate() {
-    for (&ch, acts) in &mut t.act {
-      match acts.as_slice() {
-        [] | [_] => {}
-        &[a0, a1] => match (a0, a1) {
-          (Reduce(r1), Reduce(r2)) =>
-            *acts = match (g.prod[r1 as usize].pri, g.prod[r2 as usize].pri) {
-              (Some(p1), Some(p2)) if p1 != p2 => smallvec![Reduce(if p1 < p2 { r2 } else { r1 })],
-              _ => {
-                reports.push(Conflict { kind: ConflictKind::RR { r1, r2 }, state: idx as u32, ch });
-                smallvec![Reduce(r1.min(r2)), Reduce(r1.max(r2))]
-              }
-            },
-          (Reduce(r), Shift(s)) | (Shift(s), Reduce(r)) =>
-            *acts = match (g.prod[r as usize].pri, g.terms[ch as usize].pri_assoc) {
-              (Some(pp), Some((cp, ca))) => match pp.cmp(&cp) {
-                Less => smallvec![Shift(s)], Greater => smallvec![Reduce(r)],
-                Equal => match ca {
-                  Assoc::Left => smallvec![Reduce(r)],
-                  Assoc::Right => smallvec![Shift(s)],
-                  Assoc::NoAssoc => smallvec![],
-                }
-              },
-              _ => {
-                reports.push(Conflict { kind: ConflictKind::SR { s, r }, state: idx as u32, ch });
-                smallvec![Shift(s), Reduce(r)]
-              }
-            },
-          _ => unreachable!("there should be a bug in lr"),
+    for &mut (ch, ref mut acts) in &mut t.act {
+      if acts.len() < 2 { continue; }
+      let state = idx as u32;
+      let mut removed = vec![false; acts.len()];
+      let (mut resolved, mut by_pri) = (None, false);
+      for i in 0..acts.len() {
+        for j in i + 1..acts.len() {
+          if let Some((ri, rj, r)) = decide(g, ch, acts[i], acts[j], &mut used) {
+            removed[i] |= ri;
+            removed[j] |= rj;
+            resolved = resolved.or(r);
+            by_pri |= r.is_none();
+          }
         }
-        _ => reports.push(Conflict { kind: ConflictKind::Many(acts.clone()), state: idx as u32, ch }),
       }
+      let mut rest = acts.iter().zip(removed).filter(|x| !x.1).map(|x| *x.0).collect::<Acts>();
+      rest.sort_unstable_by_key(default_order);
+      if rest.len() > 1 {
+        reports.push(Conflict { kind: conflict_kind(&rest), state, ch, resolved: None, lalr_only: None });
+      } else if let Some(i) = resolved {
+        acts.sort_unstable_by_key(default_order);
+        reports.push(Conflict { kind: conflict_kind(acts), state, ch, resolved: Some(Resolved::Directive(i)), lalr_only: None });
+      } else if by_pri && acts.len() > 2 {
+        acts.sort_unstable_by_key(default_order);
+        // stable sort, the kept action goes first
+        acts.sort_by_key(|a| !rest.contains(a));
+        reports.push(Conflict { kind: ConflictKind::Many(acts.clone()), state, ch, resolved: Some(Resolved::Priority(!rest.is_empty())), lalr_only: None });
+      }
+      *acts = rest;
+    }
+  }
+  (reports, used)
+}
+
+// a reduce-reduce conflict of lalr1 may be introduced by merging canonical lr1 states with the same core
+// for every unresolved reduce-reduce conflict in `conflict` (from the lalr1 fsm `lalr1`), check whether the canonical lr1 fsm `lr1`
+// has a state with the same core that reduces both productions on the same token, if not, set `lalr_only`
+pub fn mark_lalr_only(conflict: &mut [Conflict], lalr1: &Lr1Fsm, lr1: &Lr1Fsm) {
+  let reduces = |item: &Lr1Item, r: u32, ch: u32| item.prod_id == r && item.dot == item.prod.len() as u32 && bitset::ibs(&item.lookahead).get(ch as usize);
+  for c in conflict {
+    if let (ConflictKind::RR { r1, r2 }, None) = (&c.kind, c.resolved) {
+      let (r1, r2, ch) = (*r1, *r2, c.ch);
+      let core = &lalr1[c.state as usize].closure;
+      let mut merged = Vec::new();
+      let mut both = false;
+      for (i, s) in lr1.iter().enumerate() {
+        if s.closure.len() != core.len() || s.closure.iter().zip(core.iter()).any(|(x, y)| x.lr0 != y.lr0) { continue; }
+        let (has1, has2) = (s.closure.iter().any(|x| reduces(x, r1, ch)), s.closure.iter().any(|x| reduces(x, r2, ch)));
+        both |= has1 && has2;
+        if has1 { merged.push((i as u32, r1)); }
+        if has2 { merged.push((i as u32, r2)); }
+      }
+      if !both { c.lalr_only = Some(merged); }
     }
   }
-  reports
-}
\ No newline at end of file
+}
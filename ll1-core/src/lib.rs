@@ -1,3 +1,8 @@
+pub mod llk;
+pub mod sets;
+
+pub use sets::Nullable;
+
 use common::*;
 
 // both First and Follow are equivalent to vec![BitSet(g.token_num()); g.nt.len()]
@@ -107,7 +112,8 @@ impl Follow {
   pub fn get(&self, i: usize) -> &[u32] { &self.bitsets[i * self.elem_len..(i + 1) * self.elem_len] }
 }
 
-pub type LLTable = Vec<HashMap<u32, SmallVec<[u32; 2]>>>;
+// for each non-terminal, (terminal, prod ids) sorted by terminal, so that the iteration order is deterministic
+pub type LLTable = Vec<Vec<(u32, SmallVec<[u32; 2]>)>>;
 
 // first set and ps set are useless for parser generating, if you need them, `LLCtx::new` have these 2 local variables
 // update: now first set is added back into LLCtx, but only for printing
@@ -124,10 +130,12 @@ impl LLCtx {
   pub fn new(g: &Grammar) -> LLCtx {
     let first = First::new(g);
     let follow = Follow::new(g, &first);
+    // infix and postfix productions of pratt non-terminals are parsed by precedence climbing
+    let pratt_op = g.pratt.iter().flat_map(|x| x.infix.iter().chain(x.postfix.iter())).map(|x| x.prod).collect::<HashSet<_>>();
     let mut ps = Vec::new();
     for i in 0..g.nt.len() {
       let mut psi = IndexMap::default();
-      for prod in g.get_prod(i) {
+      for prod in g.get_prod(i).iter().filter(|x| !pratt_op.contains(&x.id)) {
         let mut predict = first.first(&prod.rhs, g);
         let mut predict_bs = bitset::bs(&mut predict);
         if predict_bs.as_imm().get(EPS_IDX) {
@@ -140,14 +148,114 @@ impl LLCtx {
     }
     let mut table = Vec::new();
     for ps in &ps {
-      let mut tbi = HashMap::default();
+      let mut tbi = vec![SmallVec::new(); g.terms.len()];
       for (&prod, predict) in ps {
-        bitset::ibs(predict).ones(|i| {
-          tbi.entry(i as u32).or_insert_with(SmallVec::new).push(prod);
-        });
+        bitset::ibs(predict).ones(|i| tbi[i].push(prod));
       }
-      table.push(tbi);
+      table.push(tbi.into_iter().enumerate().filter(|(_, x)| !x.is_empty()).map(|(i, x)| (i as u32, x)).collect());
     }
     LLCtx { first, follow, table }
   }
-}
\ No newline at end of file
+}
+
+// how a token gets into a follow set, see `explain_follow`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FollowReason {
+  // `_Eof` is in the follow set of the start non-terminal
+  Init,
+  // the non-terminal is at `pos` of production `prod`, and the token is in FIRST of the symbols after it
+  First { prod: u32, pos: u32 },
+  // the non-terminal is at `pos` of production `prod`, the symbols after it are nullable,
+  // so the token is propagated from the follow set of the lhs of `prod` (the previous non-terminal in the chain)
+  Lhs { prod: u32, pos: u32 },
+}
+
+// explain why `token` is in FOLLOW(nt), `nt` is an index in `g.nt`
+// the result is a shortest chain of (non-terminal, reason), starting from where the token is generated, and ending with `nt`
+// return None if the token is not in FOLLOW(nt)
+pub fn explain_follow(g: &Grammar, first: &First, follow: &Follow, nt: usize, token: u32) -> Option<Vec<(u32, FollowReason)>> {
+  if nt >= g.nt.len() || token as usize >= g.terms.len() || !bitset::ibs(follow.get(nt)).get(token as usize) { return None; }
+  // searching backward from `nt`, `next` maps a non-terminal to the one after it in the chain, and the reason of that one
+  let mut next = vec![None; g.nt.len()];
+  let mut vis = vec![false; g.nt.len()];
+  vis[nt] = true;
+  let mut q = std::collections::VecDeque::new();
+  q.push_back(nt);
+  while let Some(a) = q.pop_front() {
+    let mut origin = if a == g.start().0 as usize && token as usize == EOF_IDX { Some(FollowReason::Init) } else { None };
+    let mut prev = Vec::new();
+    for prod in &g.prod {
+      for (pos, &ch) in prod.rhs.iter().enumerate() {
+        if origin.is_some() { break; }
+        if g.as_nt(ch) != Some(a) { continue; }
+        let beta_first = first.first(&prod.rhs[pos + 1..], g);
+        let (prod, pos, lhs) = (prod.id, pos as u32, prod.lhs as usize);
+        if bitset::ibs(&beta_first).get(token as usize) {
+          origin = Some(FollowReason::First { prod, pos });
+        } else if bitset::ibs(&beta_first).get(EPS_IDX) && bitset::ibs(follow.get(lhs)).get(token as usize) {
+          prev.push((lhs, FollowReason::Lhs { prod, pos }));
+        }
+      }
+    }
+    if let Some(origin) = origin {
+      let mut chain = vec![(a as u32, origin)];
+      let mut cur = a;
+      while let Some((n, r)) = next[cur] {
+        chain.push((n as u32, r));
+        cur = n;
+      }
+      return Some(chain);
+    }
+    for (lhs, r) in prev {
+      if !vis[lhs] {
+        vis[lhs] = true;
+        next[lhs] = Some((a, r));
+        q.push_back(lhs);
+      }
+    }
+  }
+  None
+}
+
+// explain why `token` is in FIRST(nt), `nt` is an index in `g.nt`
+// the result is a shortest chain of (prod, pos), the first prod's lhs is `nt`, and the symbols before `pos` of each prod are nullable
+// the symbol at `pos` is the lhs of the next prod, or `token` for the last prod
+// return None if the token is not in FIRST(nt)
+pub fn explain_first(g: &Grammar, first: &First, nt: usize, token: u32) -> Option<Vec<(u32, u32)>> {
+  if nt >= g.nt.len() || token as usize >= g.terms.len() || !bitset::ibs(first.get(nt)).get(token as usize) { return None; }
+  // `prev` maps a non-terminal to the (prod, pos) that reaches it
+  let mut prev = vec![None; g.nt.len()];
+  let mut vis = vec![false; g.nt.len()];
+  vis[nt] = true;
+  let mut q = std::collections::VecDeque::new();
+  q.push_back(nt);
+  while let Some(a) = q.pop_front() {
+    for prod in g.get_prod(a) {
+      for (pos, &ch) in prod.rhs.iter().enumerate() {
+        let step = (prod.id, pos as u32);
+        match g.as_nt(ch) {
+          None if ch == token => {
+            let mut chain = vec![step];
+            let mut cur = a;
+            while let Some((p, pos)) = prev[cur] {
+              chain.push((p, pos));
+              cur = g.prod[p as usize].lhs as usize;
+            }
+            chain.reverse();
+            return Some(chain);
+          }
+          None => break,
+          Some(b) => {
+            if !vis[b] && bitset::ibs(first.get(b)).get(token as usize) {
+              vis[b] = true;
+              prev[b] = Some(step);
+              q.push_back(b);
+            }
+            if !bitset::ibs(first.get(b)).get(EPS_IDX) { break; }
+          }
+        }
+      }
+    }
+  }
+  None
+}
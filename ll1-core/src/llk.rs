@@ -0,0 +1,111 @@
+// solve the conflicting entries of `LLTable` by more than one token of lookahead
+// FIRST_k and FOLLOW_k are only computed when there are conflicts, since they can be very large
+use crate::*;
+
+// a string of at most k terminals, a string shorter than k in FOLLOW_k (and predict set) ends with `_Eof`
+pub type Seq = SmallVec<[u32; 4]>;
+
+// an entry of `LLTable` (non-terminal `nt` when seeing `term`) that is solved by `k` tokens of lookahead
+pub struct LLkEntry {
+  pub nt: u32,
+  pub term: u32,
+  pub k: u32,
+  // (the k - 1 tokens after `term`, production), sorted by tokens, a string shorter than k is padded with `_Eof`
+  pub alt: Vec<(Seq, u32)>,
+}
+
+fn complete(s: &Seq, k: usize) -> bool { s.len() == k || s.last() == Some(&(EOF_IDX as u32)) }
+
+// the first k terminals of the concatenation of every string in `a` and every string in `b`
+fn concat(a: &HashSet<Seq>, b: &HashSet<Seq>, k: usize) -> HashSet<Seq> {
+  let mut ret = HashSet::default();
+  for x in a {
+    if complete(x, k) {
+      ret.insert(x.clone());
+    } else {
+      for y in b {
+        let mut s = x.clone();
+        s.extend(y.iter().take(k - x.len()).copied());
+        ret.insert(s);
+      }
+    }
+  }
+  ret
+}
+
+fn first_k_of(string: &[u32], first: &[HashSet<Seq>], g: &Grammar, k: usize) -> HashSet<Seq> {
+  let mut ret = HashSet::default();
+  ret.insert(Seq::new());
+  for &ch in string {
+    if ret.iter().all(|x| complete(x, k)) { break; }
+    ret = match g.as_nt(ch) {
+      Some(ch) => concat(&ret, &first[ch], k),
+      None => concat(&ret, &std::iter::once(std::iter::once(ch).collect()).collect(), k),
+    };
+  }
+  ret
+}
+
+pub fn first_k(g: &Grammar, k: usize) -> Vec<HashSet<Seq>> {
+  let mut first = vec![HashSet::default(); g.nt.len()];
+  loop {
+    let mut changed = false;
+    for i in 0..g.nt.len() {
+      for prod in g.get_prod(i) {
+        for s in first_k_of(&prod.rhs, &first, g, k) { changed |= first[i].insert(s); }
+      }
+    }
+    if !changed { break first; }
+  }
+}
+
+pub fn follow_k(g: &Grammar, first: &[HashSet<Seq>], k: usize) -> Vec<HashSet<Seq>> {
+  let mut follow = vec![HashSet::default(); g.nt.len()];
+  follow[g.start().0 as usize].insert(std::iter::once(EOF_IDX as u32).collect());
+  loop {
+    let mut changed = false;
+    for prod in &g.prod {
+      for (i, &ch) in prod.rhs.iter().enumerate() {
+        if let Some(ch) = g.as_nt(ch) {
+          let remain = first_k_of(&prod.rhs[i + 1..], first, g, k);
+          for s in concat(&remain, &follow[prod.lhs as usize], k) { changed |= follow[ch].insert(s); }
+        }
+      }
+    }
+    if !changed { break follow; }
+  }
+}
+
+// try to solve the entries with multiple productions in `table` by k = 2, 3, ..., max_k tokens of lookahead
+// this is strong LL(k), i.e., FOLLOW_k of the non-terminal is used instead of the context where it appears
+pub fn solve(g: &Grammar, table: &LLTable, max_k: u32) -> Vec<LLkEntry> {
+  let mut remain = Vec::new();
+  for (nt, t) in table.iter().enumerate() {
+    for &(term, ref prods) in t {
+      if prods.len() > 1 { remain.push((nt as u32, term, prods)); }
+    }
+  }
+  let mut ret = Vec::new();
+  for k in 2..=max_k as usize {
+    if remain.is_empty() { break; }
+    let first = first_k(g, k);
+    let follow = follow_k(g, &first, k);
+    remain.retain(|&(nt, term, prods)| {
+      let mut alt = HashMap::default();
+      for &prod in prods.iter() {
+        let predict = concat(&first_k_of(&g.prod[prod as usize].rhs, &first, g, k), &follow[nt as usize], k);
+        for mut s in predict.into_iter().filter(|s| s[0] == term) {
+          s.resize(k, EOF_IDX as u32);
+          s.remove(0);
+          if *alt.entry(s).or_insert(prod) != prod { return true; }
+        }
+      }
+      let mut alt = alt.into_iter().collect::<Vec<_>>();
+      alt.sort_unstable();
+      ret.push(LLkEntry { nt, term, k: k as u32, alt });
+      false
+    });
+  }
+  ret.sort_unstable_by_key(|x| (x.nt, x.term));
+  ret
+}
@@ -0,0 +1,112 @@
+// safe queries of FIRST/FOLLOW/nullable, returning terminal ids instead of bitset words, and dumping them as text or json
+use crate::*;
+
+// whether each non-terminal can derive the empty string
+pub struct Nullable { nullable: Vec<bool> }
+
+impl Nullable {
+  pub fn new(g: &Grammar) -> Nullable {
+    let mut nullable = vec![false; g.nt.len()];
+    loop {
+      let mut changed = false;
+      for prod in &g.prod {
+        if !nullable[prod.lhs as usize] && prod.rhs.iter().all(|&x| g.as_nt(x).map_or(false, |x| nullable[x])) {
+          nullable[prod.lhs as usize] = true;
+          changed = true;
+        }
+      }
+      if !changed { break Nullable { nullable }; }
+    }
+  }
+
+  // `nt` is an index in `g.nt`
+  pub fn get(&self, nt: usize) -> bool { self.nullable[nt] }
+
+  // whether `string` (terminal and non-terminal ids) can derive the empty string
+  pub fn of(&self, string: &[u32], g: &Grammar) -> bool {
+    string.iter().all(|&x| g.as_nt(x).map_or(false, |x| self.nullable[x]))
+  }
+}
+
+// terminal ids in a bitset, without `_Eps`
+fn ones(set: &[u32]) -> Vec<u32> {
+  let mut ret = Vec::new();
+  bitset::ibs(set).ones(|i| if i != EPS_IDX { ret.push(i as u32); });
+  ret
+}
+
+impl First {
+  // terminals in FIRST(nt), `nt` is an index in `g.nt`
+  pub fn terms(&self, nt: usize) -> Vec<u32> { ones(self.get(nt)) }
+
+  // terminals in FIRST(string), `string` consists of terminal and non-terminal ids
+  pub fn terms_of(&self, string: &[u32], g: &Grammar) -> Vec<u32> {
+    assert!(string.iter().all(|&x| (x as usize) < g.token_num()), "invalid token id in {:?}", string);
+    ones(&self.first(string, g))
+  }
+}
+
+impl Follow {
+  // terminals in FOLLOW(nt), `nt` is an index in `g.nt`
+  pub fn terms(&self, nt: usize) -> Vec<u32> { ones(self.get(nt)) }
+}
+
+pub fn names<'a>(g: &'a Grammar, ids: &[u32]) -> Vec<&'a str> {
+  ids.iter().map(|&x| g.show_token(x as _)).collect()
+}
+
+// one non-terminal per paragraph:
+// Expr:
+//   nullable: false
+//   first: IntLit LPar
+//   follow: _Eof RPar
+pub fn dump_text<'a>(g: &'a Grammar, first: &'a First, follow: &'a Follow) -> impl Display + 'a {
+  fmt_::fn2display(move |f| {
+    let nullable = Nullable::new(g);
+    for (i, nt) in g.nt.iter().enumerate() {
+      writeln!(f, "{}:", nt.name)?;
+      writeln!(f, "  nullable: {}", nullable.get(i))?;
+      writeln!(f, "  first: {}", names(g, &first.terms(i)).join(" "))?;
+      writeln!(f, "  follow: {}", names(g, &follow.terms(i)).join(" "))?;
+    }
+    Ok(())
+  })
+}
+
+fn json_str(f: &mut Formatter, s: &str) -> FmtResult {
+  f.write_str("\"")?;
+  for ch in s.chars() {
+    match ch {
+      '"' => f.write_str("\\\"")?,
+      '\\' => f.write_str("\\\\")?,
+      ch if (ch as u32) < 0x20 => write!(f, "\\u{:04x}", ch as u32)?,
+      ch => write!(f, "{}", ch)?,
+    }
+  }
+  f.write_str("\"")
+}
+
+// an object mapping each non-terminal name to {"nullable": bool, "first": [names], "follow": [names]}, in the order of `g.nt`
+pub fn dump_json<'a>(g: &'a Grammar, first: &'a First, follow: &'a Follow) -> impl Display + 'a {
+  fmt_::fn2display(move |f| {
+    let nullable = Nullable::new(g);
+    let show_set = |f: &mut Formatter, name: &str, set: Vec<u32>| {
+      write!(f, ",\n    \"{}\": [", name)?;
+      for (i, x) in names(g, &set).into_iter().enumerate() {
+        if i != 0 { f.write_str(", ")?; }
+        json_str(f, x)?;
+      }
+      f.write_str("]")
+    };
+    f.write_str("{")?;
+    for (i, nt) in g.nt.iter().enumerate() {
+      f.write_str(if i == 0 { "\n  " } else { ",\n  " })?;
+      json_str(f, nt.name)?;
+      write!(f, ": {{\n    \"nullable\": {}", nullable.get(i))?;
+      show_set(f, "first", first.terms(i))?;
+      show_set(f, "follow", follow.terms(i))?;
+      f.write_str("\n  }")?;
+    }
+    f.write_str("\n}\n")
+  })
+}
@@ -0,0 +1,18 @@
+// prints the tokens read from stdin by the `StreamLexer` of a parser generated with `--stream`:
+// $ cargo run --bin parser_gen --features="clap toml" -- examples/calc_java.toml -o Parser.java -l java --stream
+// $ javac -d . Parser.java examples/StreamJava.java && java StreamJava < input
+public class StreamJava {
+  public static void main(String[] args) throws java.io.IOException {
+    Parser.StreamLexer lexer = new Parser.StreamLexer(System.in);
+    int count = 0;
+    while (true) {
+      Parser.Token t = lexer.next();
+      // the kinds are the indices of terminals, which start with `_Eps`, `_Eof` and `_Err`
+      if (t.kind == 1) { break; }
+      // `t.piece` is a new `String`, so it can be kept after the next call of `lexer.next()`
+      System.out.println(t.line + ":" + t.col + " " + t.kind + " " + t.piece);
+      ++count;
+    }
+    System.out.println(count + " tokens");
+  }
+}
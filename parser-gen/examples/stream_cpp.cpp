@@ -0,0 +1,18 @@
+// prints the tokens read from stdin by the `StreamLexer` of a parser generated with `--stream`:
+// $ cargo run --bin parser_gen --features="clap toml" -- examples/calc_cpp.toml -o calc.cpp -l cpp --stream
+// $ g++ -std=c++17 -I. examples/stream_cpp.cpp -o stream && ./stream < input
+#include "calc.cpp"
+#include <iostream>
+
+int main() {
+  StreamLexer lexer(std::cin);
+  u32 count = 0;
+  while (true) {
+    Token t = lexer.next();
+    if (t.kind == Token::_Eof) { break; }
+    // `t.piece` refers to the buffer of `lexer`, and is only valid until the next call of `lexer.next()`
+    std::cout << t.line << ':' << t.col << ' ' << t.kind << ' ' << t.piece << '\n';
+    ++count;
+  }
+  std::cout << count << " tokens" << std::endl;
+}
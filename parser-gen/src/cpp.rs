@@ -1,11 +1,11 @@
 use crate::*;
 
 impl<W: std::io::Write> Config<'_, W> {
-  pub fn cpp_lalr1(&mut self, g: &Grammar, table: &Table, dfa: &Dfa) -> Result<()> {
+  fn cpp_common(&mut self, g: &Grammar, dfa: &Dfa) -> Result<()> {
     let (types, _) = fmt::gather_types(g);
     let parse_res = g.nt.last().unwrap().ty;
     write!(
-      self.code_output, include_str!("template/lalr1.cpp.template"),
+      self.code_output, include_str!("template/common.cpp.template"),
       include = g.raw.include,
       token_kind = fmt::comma_sep(g.terms.iter().map(|x| x.name)),
       stack_item = types.join(","),
@@ -19,25 +19,57 @@ impl<W: std::io::Write> Config<'_, W> {
       parser_struct = fmt_::fn2display(move |f| if g.raw.parser_def.is_none() {
         writeln!(f, r"struct Parser{{std::variant<{},Token>parse(Lexer&lexer);{}}};", parse_res, g.raw.parser_field)
       } else { Ok(()) }),
+    )?;
+    if self.stream { write!(self.code_output, include_str!("template/stream.cpp.template"))?; }
+    Ok(())
+  }
+
+  // is_pair == true: `stk` is std::vector<std::pair<StackItem, integer>>; is_pair == false: `stk` is std::vector<StackItem>
+  fn cpp_act<'a>(g: &'a Grammar, is_pair: bool) -> impl std::fmt::Display + 'a {
+    fmt_::fn2display(move |f| (for (i, prod) in g.prod.iter().enumerate() {
+      write!(f, "case {}:{{", i)?;
+      for (j, &x) in prod.rhs.iter().enumerate().rev() {
+        let name = fmt_::fn2display(move |f|
+          match prod.args { Some(args) => f.write_str(args[j].0), None => write!(f, "_{}", j + 1) });
+        let ty = if let Some(x) = g.as_nt(x) { g.nt[x].ty } else { "Token" };
+        writeln!(f, "[[maybe_unused]]{1} {}(std::move(*std::get_if<{1}>(&stk.back(){2})));stk.pop_back();", name, ty, if is_pair { ".first" } else { "" })?;
+      }
+      writeln!(f, "{}break;}}", if i == g.prod.len() - 1 { "__=_1;" } else { prod.act })?;
+    }, Ok(())).1)
+  }
+
+  pub fn cpp_lalr1(&mut self, g: &Grammar, table: &Table, dfa: &Dfa) -> Result<()> {
+    self.cpp_common(g, dfa)?;
+    write!(
+      self.code_output, include_str!("template/lalr1.cpp.template"),
       u_lr_fsm_size = fmt::min_u(table.len()),
       u_act_size = fmt::min_u(table.len() * 4),
       parser_type = g.raw.parser_def.unwrap_or("Parser"),
-      res_type = parse_res,
+      res_type = g.nt.last().unwrap().ty,
       prod = fmt::comma_sep(g.prod.iter().map(|x| x.lhs)),
       term_num = g.terms.len(),
       nt_num = g.nt.len(),
       action = fmt::action(g, table, ('{', '}')),
       goto = fmt::goto(g, table, ('{', '}')),
-      parser_act = fmt_::fn2display(|f| (for (i, prod) in g.prod.iter().enumerate() {
-        write!(f, "case {}:{{", i)?;
-        for (j, &x) in prod.rhs.iter().enumerate().rev() {
-          let name = fmt_::fn2display(move |f|
-            match prod.args { Some(args) => f.write_str(args[j].0), None => write!(f, "_{}", j + 1) });
-          let ty = if let Some(x) = g.as_nt(x) { g.nt[x].ty } else { "Token" };
-          writeln!(f, "[[maybe_unused]]{1} {}(std::move(*std::get_if<{1}>(&stk.back().first)));stk.pop_back();", name, ty)?;
-        }
-        writeln!(f, "{}break;}}", if i == g.prod.len() - 1 { "__=_1;" } else { prod.act })?;
-      }, Ok(())).1)
+      parser_act = Self::cpp_act(g, true),
+    )
+  }
+
+  pub fn cpp_ll1(&mut self, g: &Grammar, ll: &LLCtx, llk: &[LLkEntry], dfa: &Dfa) -> Result<()> {
+    self.cpp_common(g, dfa)?;
+    write!(
+      self.code_output, include_str!("template/ll1.cpp.template"),
+      parser_type = g.raw.parser_def.unwrap_or("Parser"),
+      res_type = g.nt.last().unwrap().ty,
+      u_prod_size = fmt::min_u(g.prod.len() + 1),
+      term_num = g.terms.len(),
+      token_num = g.token_num(),
+      table = fmt::ll_table(g, ll, ('{', '}')),
+      prod_rhs = fmt::prod_rhs(g),
+      prod_off = fmt::prod_off(g),
+      ll_k = fmt::ll_k(llk),
+      res_nt_id = g.token_num() - 1,
+      parser_act = Self::cpp_act(g, false),
     )
   }
-}
\ No newline at end of file
+}
@@ -24,6 +24,11 @@ pub fn gather_types<'a>(g: &Grammar<'a>) -> (Vec<&'a str>, HashMap<&'a str, u32>
   (types, types2id)
 }
 
+// whether `ty` has lifetime `lt` (e.g. `'p`), not counting longer lifetimes that start with it
+pub fn has_lifetime(ty: &str, lt: &str) -> bool {
+  ty.match_indices(lt).any(|(i, _)| !ty[i + lt.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+}
+
 pub fn acc<'a>(g: &'a Grammar, dfa: &'a Dfa, namespace: &'a str) -> impl Display + 'a {
   fmt_::fn2display(move |f| (for &(acc, _) in &dfa.nodes {
     match acc {
@@ -46,29 +51,185 @@ pub fn dfa_edge<'a>(dfa: &'a Dfa, bracket: (char, char)) -> impl Display + 'a {
   })
 }
 
+// dfa in dot file format, edges to the same target are merged into one, and sorted by (target, equivalent class)
+pub fn dfa_dot(dfa: &Dfa) -> impl Display + '_ {
+  fmt_::fn2display(move |f| {
+    f.write_str("digraph {\n")?;
+    for (i, (acc, edges)) in dfa.nodes.iter().enumerate() {
+      match acc {
+        Some(acc) => writeln!(f, r#"{}[shape=doublecircle, label="{}\n{}"];"#, i, i, acc)?,
+        None => writeln!(f, "{}[shape=circle];", i)?,
+      }
+      let mut edges = edges.iter().map(|(&k, &out)| (out, k)).collect::<Vec<_>>();
+      edges.sort_unstable();
+      let mut j = 0;
+      while j < edges.len() {
+        let out = edges[j].0;
+        write!(f, r#"{} -> {} [label=""#, i, out)?;
+        let len = edges[j..].iter().take_while(|x| x.0 == out).count();
+        write!(f, "{}", fmt_::sep(edges[j..j + len].iter().map(|x| x.1), ","))?;
+        j += len;
+        f.write_str("\"];\n")?;
+      }
+    }
+    f.write_str("}")
+  })
+}
+
 pub fn goto<'a>(g: &'a Grammar, table: &'a Table, bracket: (char, char)) -> impl Display + 'a {
   fmt_::fn2display(move |f| {
     for t in table {
       // iterate over all non-terminals
-      let goto = comma_sep((g.terms.len()..g.token_num()).map(|x| t.goto.get(&(x as u32)).unwrap_or(&0)));
+      let goto = comma_sep((g.terms.len()..g.token_num()).map(|x| sorted_get(&t.goto, x as u32).unwrap_or(&0)));
       write!(f, "{}{}{},", bracket.0, goto, bracket.1)?;
     }
     Ok(())
   })
 }
 
+// encode an action as (2 bit tag, val) pair, error is encoded as tag 3
+#[inline(always)]
+pub fn encode_act(act: Act) -> u32 {
+  let (tag, val) = match act { Act::Acc => (2, 0), Act::Shift(x) => (0, x), Act::Reduce(x) => (1, x) };
+  tag | (val << 2)
+}
+
 pub fn action<'a>(g: &'a Grammar, table: &'a Table, bracket: (char, char)) -> impl Display + 'a {
   fmt_::fn2display(move |f| {
     for TableEntry { act, .. } in table {
       f.write_char(bracket.0)?;
       for i in 0..g.terms.len() as u32 {
-        let (tag, val) = act.get(&i).and_then(|x| x.get(0))
-          .map(|&x| match x { Act::Acc => (2, 0), Act::Shift(x) => (0, x), Act::Reduce(x) => (1, x) })
-          .unwrap_or((3, 0));
-        write!(f, "{},", tag | (val << 2))?;
+        let act = sorted_get(act, i).and_then(|x| x.get(0)).map(|&x| encode_act(x)).unwrap_or(3);
+        write!(f, "{},", act)?;
       };
       write!(f, "{},", bracket.1)?;
     }
     Ok(())
   })
-}
\ No newline at end of file
+}
+
+// the terminals that have an action in each state, i.e., the tokens acceptable by the state, except `_Err` (the `error` token)
+pub fn expected<'a>(g: &'a Grammar, table: &'a Table) -> impl Display + 'a {
+  fmt_::fn2display(move |f| {
+    for TableEntry { act, .. } in table {
+      f.write_str("&[")?;
+      for &(i, ref x) in act {
+        if !x.is_empty() && i != ERR_IDX as u32 { write!(f, "TokenKind::{},", g.terms[i as usize].name)?; }
+      }
+      f.write_str("],")?;
+    }
+    Ok(())
+  })
+}
+
+// the number of entries in `table` that have more than one action (i.e., unsolved conflicts)
+pub fn multi_act_num(table: &Table) -> usize {
+  table.iter().map(|t| t.act.iter().filter(|(_, x)| x.len() > 1).count()).sum()
+}
+
+// like `action`, but all actions are kept: an entry with more than one action is encoded as tag 3, val = 1 + its index in `multi_act`
+pub fn glr_action<'a>(g: &'a Grammar, table: &'a Table) -> impl Display + 'a {
+  fmt_::fn2display(move |f| {
+    let mut multi_idx = 0;
+    for TableEntry { act, .. } in table {
+      f.write_char('[')?;
+      for i in 0..g.terms.len() as u32 {
+        let act = match sorted_get(act, i).map(|x| x.as_slice()) {
+          Some(&[x]) => encode_act(x),
+          Some(x) if x.len() > 1 => (multi_idx += 1, 3 | (multi_idx << 2)).1,
+          _ => 3,
+        };
+        write!(f, "{},", act)?;
+      };
+      f.write_str("],")?;
+    }
+    Ok(())
+  })
+}
+
+// the actions of entries that have more than one action, in the same order as they are referred in `glr_action`
+pub fn multi_act<'a>(table: &'a Table) -> impl Display + 'a {
+  fmt_::fn2display(move |f| {
+    for TableEntry { act, .. } in table {
+      for (_, x) in act.iter().filter(|(_, x)| x.len() > 1) {
+        write!(f, "&[{}],", comma_sep(x.iter().map(|&x| encode_act(x))))?;
+      }
+    }
+    Ok(())
+  })
+}
+
+// dense ll1 table for each non-terminal, the entry is 1 + the predicted production id, or 0 for error
+pub fn ll_table<'a>(g: &'a Grammar, ll: &'a LLCtx, bracket: (char, char)) -> impl Display + 'a {
+  fmt_::fn2display(move |f| {
+    for t in &ll.table {
+      let row = comma_sep((0..g.terms.len() as u32).map(move |i| sorted_get(t, i).map(|x| x[0] + 1).unwrap_or(0)));
+      write!(f, "{}{}{},", bracket.0, row, bracket.1)?;
+    }
+    Ok(())
+  })
+}
+
+// rhs of all productions concatenated, production i is PROD_RHS[PROD_OFF[i]..PROD_OFF[i + 1]]
+pub fn prod_rhs<'a>(g: &'a Grammar) -> impl Display + 'a {
+  comma_sep(g.prod.iter().flat_map(|x| x.rhs.iter()))
+}
+
+pub fn prod_off<'a>(g: &'a Grammar) -> impl Display + 'a {
+  comma_sep(std::iter::once(0).chain(g.prod.iter().scan(0, |off, x| (*off += x.rhs.len(), Some(*off)).1)))
+}
+
+// entries solved by more than one token of lookahead, each is [nt, term, k, n, n * (k - 1 tokens, production)]
+pub fn ll_k<'a>(llk: &'a [LLkEntry]) -> impl Display + 'a {
+  fmt_::fn2display(move |f| {
+    for e in llk {
+      write!(f, "{},{},{},{},", e.nt, e.term, e.k, e.alt.len())?;
+      for (s, prod) in &e.alt {
+        for x in s { write!(f, "{},", x)?; }
+        write!(f, "{},", prod)?;
+      }
+    }
+    Ok(())
+  })
+}
+
+// see `LL_K` in "template/ll1_parse.rs.template", `llk` is already sorted by (nt, term)
+pub fn ll_k_rs<'a>(llk: &'a [LLkEntry]) -> impl Display + 'a {
+  fmt_::fn2display(move |f| {
+    for e in llk {
+      write!(f, "(({},{}),{},&[", e.nt, e.term, e.k)?;
+      for (s, prod) in &e.alt {
+        for x in s { write!(f, "{},", x)?; }
+        write!(f, "{},", prod)?;
+      }
+      f.write_str("]),")?;
+    }
+    Ok(())
+  })
+}
+
+// see `PRATT_OP` in "template/ll1_parse.rs.template"
+pub fn pratt_op<'a>(g: &'a Grammar) -> impl Display + 'a {
+  fmt_::fn2display(move |f| {
+    for i in 0..g.nt.len() as u32 {
+      f.write_str("&[")?;
+      if let Some(p) = g.pratt.iter().find(|x| x.nt == i) {
+        let mut ops = p.infix.iter().map(|x| (x, match x.assoc { Assoc::Left => 0, Assoc::Right => 1, Assoc::NoAssoc => 2 }))
+          .chain(p.postfix.iter().map(|x| (x, 3))).collect::<Vec<_>>();
+        ops.sort_unstable_by_key(|x| x.0.op);
+        for (x, kind) in ops { write!(f, "({},{},{},{}),", x.op, x.prod, x.pri, kind)?; }
+      }
+      f.write_str("],")?;
+    }
+    Ok(())
+  })
+}
+
+pub fn pratt_prefix<'a>(g: &'a Grammar) -> impl Display + 'a {
+  fmt_::fn2display(move |f| {
+    let mut prefix = g.pratt.iter().flat_map(|x| x.prefix.iter()).map(|x| (x.prod, x.pri)).collect::<Vec<_>>();
+    prefix.sort_unstable();
+    for (prod, pri) in prefix { write!(f, "({},{}),", prod, pri)?; }
+    Ok(())
+  })
+}
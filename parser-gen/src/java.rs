@@ -1,13 +1,11 @@
 use crate::*;
 
 impl<W: std::io::Write> Config<'_, W> {
-  pub fn java_lalr1(&mut self, g: &Grammar, table: &Table, dfa: &Dfa) -> Result<()> {
-    let (types, types2id) = fmt::gather_types(g);
-    let parse_res = g.nt.last().unwrap().ty;
-    let res_id = types2id[parse_res];
+  fn java_common(&mut self, g: &Grammar, dfa: &Dfa) -> Result<()> {
+    let (types, _) = fmt::gather_types(g);
     let terms2id = g.terms.iter().enumerate().map(|(idx, t)| (t.name, idx as u32)).collect::<HashMap<_, _>>();
     write!(
-      self.code_output, include_str!("template/lalr1.java.template"),
+      self.code_output, include_str!("template/common.java.template"),
       include = g.raw.include,
       parser_type = g.raw.parser_def.unwrap_or("Parser"),
       parser_field = g.raw.parser_field,
@@ -20,24 +18,57 @@ impl<W: std::io::Write> Config<'_, W> {
       stack_item = fmt_::fn2display(move |f| (for (i, ty) in types.iter().enumerate() {
         let _ = writeln!(f, "public static final class StackItem{} extends StackItem {{ {} $; }}", i, ty);
       }, Ok(())).1),
-      res_type = parse_res,
+      res_type = g.nt.last().unwrap().ty,
+    )?;
+    if self.stream { write!(self.code_output, include_str!("template/stream.java.template"))?; }
+    Ok(())
+  }
+
+  fn java_act<'a>(g: &'a Grammar, types2id: HashMap<&'a str, u32>) -> impl std::fmt::Display + 'a {
+    fmt_::fn2display(move |f| (for (i, prod) in g.prod.iter().enumerate() {
+      let _ = write!(f, "case {}:{{", i);
+      for (j, &x) in prod.rhs.iter().enumerate().rev() {
+        let name = fmt_::fn2display(move |f|
+          match prod.args { Some(args) => f.write_str(args[j].0), None => write!(f, "${}", j + 1) });
+        let (arg_ty, item_ty) = if let Some(x) = g.as_nt(x) {
+          (g.nt[x].ty, format!("StackItem{}", types2id[g.nt[x].ty]))
+        } else { ("Token", "StackItemToken".to_owned()) };
+        let _ = writeln!(f, "{} {}=(({})stk.get(stk.size()-1)).$;stk.remove(stk.size()-1);", arg_ty, name, item_ty);
+      }
+      let _ = writeln!(f, "StackItem{0} $=new StackItem{0}();", types2id[g.nt[prod.lhs as usize].ty]);
+      let _ = writeln!(f, "{}value=$;break;}}", if i == g.prod.len() - 1 { "$.$ = $1;" } else { prod.act });
+    }, Ok(())).1)
+  }
+
+  pub fn java_lalr1(&mut self, g: &Grammar, table: &Table, dfa: &Dfa) -> Result<()> {
+    let (_, types2id) = fmt::gather_types(g);
+    let res_id = types2id[g.nt.last().unwrap().ty];
+    self.java_common(g, dfa)?;
+    write!(
+      self.code_output, include_str!("template/lalr1.java.template"),
       res_id = res_id,
       prod = fmt::comma_sep(g.prod.iter().map(|x| x.lhs)),
       action = fmt::action(g, table, ('{', '}')),
       goto = fmt::goto(g, table, ('{', '}')),
-      parser_act = fmt_::fn2display(move |f| (for (i, prod) in g.prod.iter().enumerate() {
-        let _ = write!(f, "case {}:{{", i);
-        for (j, &x) in prod.rhs.iter().enumerate().rev() {
-          let name = fmt_::fn2display(move |f|
-            match prod.args { Some(args) => f.write_str(args[j].0), None => write!(f, "${}", j + 1) });
-          let (arg_ty, item_ty) = if let Some(x) = g.as_nt(x) {
-            (g.nt[x].ty, format!("StackItem{}", types2id[g.nt[x].ty]))
-          } else { ("Token", "StackItemToken".to_owned()) };
-          let _ = writeln!(f, "{} {}=(({})stk.get(stk.size()-1)).$;stk.remove(stk.size()-1);", arg_ty, name, item_ty);
-        }
-        let _ = writeln!(f, "StackItem{0} $=new StackItem{0}();", types2id[g.nt[prod.lhs as usize].ty]);
-        let _ = writeln!(f, "{}value=$;break;}}", if i == g.prod.len() - 1 { "$.$ = $1;" } else { prod.act });
-      }, Ok(())).1)
+      parser_act = Self::java_act(g, types2id),
+    )
+  }
+
+  pub fn java_ll1(&mut self, g: &Grammar, ll: &LLCtx, llk: &[LLkEntry], dfa: &Dfa) -> Result<()> {
+    let (_, types2id) = fmt::gather_types(g);
+    let res_id = types2id[g.nt.last().unwrap().ty];
+    self.java_common(g, dfa)?;
+    write!(
+      self.code_output, include_str!("template/ll1.java.template"),
+      res_id = res_id,
+      term_num = g.terms.len(),
+      token_num = g.token_num(),
+      table = fmt::ll_table(g, ll, ('{', '}')),
+      prod_rhs = fmt::prod_rhs(g),
+      prod_off = fmt::prod_off(g),
+      ll_k = fmt::ll_k(llk),
+      res_nt_id = g.token_num() - 1,
+      parser_act = Self::java_act(g, types2id),
     )
   }
-}
\ No newline at end of file
+}
@@ -4,10 +4,11 @@ pub mod cpp;
 pub mod java;
 pub mod show_lr;
 pub mod show_ll;
+pub mod messages;
 
 use common::*;
 use lalr1_core::*;
-use ll1_core::*;
+use ll1_core::{*, llk::LLkEntry};
 use re2dfa::*;
 use std::{fs::File, io::{Result, Write, BufWriter}, fmt::Write as _};
 
@@ -20,7 +21,7 @@ pub trait Codegen {
 
   fn ll(&mut self, g: &Grammar, ll: LLCtx, dfa: &Dfa) -> Result<()>;
 
-  fn lr1(&mut self, g: &Grammar, lr1: &Lr1Fsm, dfa: &Dfa, orig_table: Table, table: Table, conflict: Vec<Conflict>) -> Result<()>;
+  fn lr1(&mut self, g: &Grammar, lr1: &Lr1Fsm, dfa: &Dfa, orig_table: Table, table: Table, conflict: Vec<Conflict>, used: PriUsed) -> Result<()>;
 }
 
 #[derive(Eq, PartialEq, Copy, Clone)]
@@ -29,6 +30,8 @@ pub enum PGAlgo { LL1, LR1, LALR1 }
 #[derive(Eq, PartialEq, Copy, Clone)]
 pub enum Lang { Rs, Cpp, Java }
 
+// construct it by `Config::new`, and then set the fields of the wanted options
+#[non_exhaustive]
 pub struct Config<'a, W> {
   pub verbose: Option<&'a str>,
   pub show_fsm: Option<&'a str>,
@@ -36,44 +39,134 @@ pub struct Config<'a, W> {
   pub log_token: bool,
   pub log_reduce: bool,
   pub use_unsafe: bool,
+  // generate a GLR parser, which tries all actions of unsolved conflicts instead of only the selected one
+  pub glr: bool,
+  // ll1 only, don't generate `_parse`, the user should implement it
+  pub user_parse: bool,
+  // ll1 only, the max number of lookahead tokens used to solve conflicts, 0 and 1 both mean only using 1 token
+  pub ll_k: u32,
+  // lalr1 (rust) only, the path of the messages file, which gives custom error messages to lr states, see `messages.rs`
+  pub messages: Option<&'a str>,
+  // lalr1 (rust) only, on syntax error, try to repair the input by inserting, deleting or replacing a few tokens
+  pub repair: bool,
+  // also generate `StreamLexer`, which reads the input from `std::io::Read` (rust), `std::istream` (cpp) or `InputStream` (java)
+  pub stream: bool,
+  // lalr1 (rust) only, also generate `Incremental`, which reparses the text after edits by reusing the unaffected part of the old tree
+  pub incremental: bool,
+  // rust only, ignore `ty` and `act` of productions, the parser builds a concrete syntax tree of `Node` instead
+  pub cst: bool,
   pub lang: Lang,
   pub on_conflict: fn(String),
   pub code_output: W,
 }
 
+impl<'a, W> Config<'a, W> {
+  // all options are off, `ll_k` is 1, and `on_conflict` prints the message to stderr
+  pub fn new(lang: Lang, code_output: W) -> Config<'a, W> {
+    Config {
+      verbose: None, show_fsm: None, show_dfa: None, log_token: false, log_reduce: false, use_unsafe: false, glr: false, user_parse: false,
+      ll_k: 1, messages: None, repair: false, stream: false, incremental: false, cst: false, lang, on_conflict: |c| eprintln!("{}", c), code_output,
+    }
+  }
+}
+
 fn write(path: &str, s: impl Display) -> Result<()> {
   write!(BufWriter::new(File::create(path)?), "{}", s)
 }
 
+impl<W: Write> Config<'_, W> {
+  // `lexer_action` is written for `Lexer`, which has the whole input
+  fn check_stream(&mut self, g: &Grammar) {
+    if self.stream && !g.raw.lexer_action.is_empty() { self.grammar_error("`lexer_action` is not supported by the stream lexer".to_owned()); }
+  }
+
+  // only the lalr1 parsers recover from syntax errors by the reserved `error` terminal
+  fn check_error_token(&mut self, g: &Grammar) {
+    if g.prod.iter().any(|p| p.rhs.contains(&(ERR_IDX as u32))) {
+      self.grammar_error(format!("the reserved terminal `{}` is only supported by lalr1 (not glr or ll1) parsers", ERROR));
+    }
+  }
+
+  // `Incremental` lexes the text by itself (the same as the stream lexer), and only builds the tree without executing semantic actions
+  fn check_incremental(&mut self, g: &Grammar) {
+    if self.glr || self.lang != Lang::Rs {
+      self.grammar_error("incremental is currently only implemented for rust lalr1 parser".to_owned());
+    }
+    if !g.raw.lexer_action.is_empty() { self.grammar_error("`lexer_action` is not supported by incremental parsing".to_owned()); }
+    if g.prod.iter().any(|p| p.rhs.contains(&(ERR_IDX as u32))) {
+      self.grammar_error(format!("the reserved terminal `{}` is not supported by incremental parsing, which doesn't recover from syntax errors", ERROR));
+    }
+  }
+
+  // `ty` and `act` can be omitted in the toml only in cst mode, where they are ignored
+  fn check_ty_act(&mut self, g: &Grammar) {
+    if self.cst { return; }
+    if let Some(nt) = g.nt.iter().find(|x| x.ty.is_empty()) {
+      self.grammar_error(format!("non-term \"{}\" has no type, it can only be omitted in cst mode", nt.name));
+    }
+    if let Some(i) = g.prod.iter().position(|x| x.act.is_empty()) {
+      self.grammar_error(format!("production \"{}\" has no action, it can only be omitted in cst mode", g.show_prod(i, None)));
+    }
+  }
+}
+
 impl<W: Write> Codegen for Config<'_, W> {
   fn dfa(&mut self, dfa: &Dfa) {
     // these 2 characteristics make lexer behaviour hard to define and make lex generator hard to write
     if dfa.nodes.is_empty() || dfa.nodes[0].0.is_some() { panic!("final dfa is not suitable for a lexer, i.e., it doesn't accept anything, or it accepts empty string"); }
     if let Some(path) = self.show_dfa {
-      write(path, dfa.print_dot()).expect("failed to write dfa");
+      write(path, fmt::dfa_dot(dfa)).expect("failed to write dfa");
     }
   }
 
   fn ll(&mut self, g: &Grammar, ll: LLCtx, dfa: &Dfa) -> Result<()> {
+    self.check_stream(g);
+    self.check_ty_act(g);
+    self.check_error_token(g);
+    let llk = ll1_core::llk::solve(g, &ll.table, self.ll_k);
     if let Some(path) = self.verbose {
-      write(path, show_ll::table(&ll, g)).expect("failed to write ll1 table");
+      write(path, show_ll::table(&ll, &llk, g)).expect("failed to write ll1 table");
+    }
+    for c in show_ll::conflict(&ll.table, &llk, g) { (self.on_conflict)(c); }
+    if self.glr { self.grammar_error("glr is only supported by lr parsers, not ll1".to_owned()); }
+    if self.incremental { self.grammar_error("incremental is currently only implemented for rust lalr1 parser".to_owned()); }
+    if self.cst && self.lang != Lang::Rs { self.grammar_error("cst is currently only implemented for rust".to_owned()); }
+    if !g.pratt.is_empty() && self.lang != Lang::Rs {
+      self.grammar_error("`pratt` is currently only implemented for rust".to_owned());
     }
-    for c in show_ll::conflict(&ll.table, g) { (self.on_conflict)(c); }
     match self.lang {
-      Lang::Rs => self.rs_ll1(&g, &ll, dfa),
-      _ => unimplemented!("ll1 codegen is currently only implemented for rust"),
+      Lang::Rs => self.rs_ll1(&g, &ll, &llk, dfa),
+      Lang::Cpp => self.cpp_ll1(&g, &ll, &llk, dfa),
+      Lang::Java => self.java_ll1(&g, &ll, &llk, dfa),
     }
   }
 
-  fn lr1(&mut self, g: &Grammar, lr1: &Lr1Fsm, dfa: &Dfa, orig_table: Table, table: Table, conflict: Vec<Conflict>) -> Result<()> {
+  fn lr1(&mut self, g: &Grammar, lr1: &Lr1Fsm, dfa: &Dfa, orig_table: Table, table: Table, conflict: Vec<Conflict>, used: PriUsed) -> Result<()> {
+    self.check_stream(g);
+    self.check_ty_act(g);
+    if self.glr { self.check_error_token(g); }
     if let Some(path) = self.verbose {
-      write(path, show_lr::table(&orig_table, &table, g)).expect("failed to write lr1 table");
+      write(path, show_lr::table(&orig_table, &table, &conflict, g)).expect("failed to write lr1 table");
     }
     if let Some(path) = self.show_fsm {
       write(path, show_lr::lr1_dot(g, &lr1)).expect("failed to write lr1 fsm");
     }
     for c in show_lr::conflict(g, &conflict) { (self.on_conflict)(c); }
-    if conflict.iter().any(Conflict::is_many) { panic!(">= 3 conflicts on one token, give up solving conflicts"); }
+    for c in show_lr::unused_priority(g, &used) { (self.on_conflict)(c); }
+    if self.messages.is_some() && (self.glr || self.lang != Lang::Rs) {
+      self.grammar_error("messages is currently only implemented for rust lalr1 parser".to_owned());
+    }
+    if self.repair && (self.glr || self.lang != Lang::Rs) {
+      self.grammar_error("repair is currently only implemented for rust lalr1 parser".to_owned());
+    }
+    if self.cst && self.lang != Lang::Rs { self.grammar_error("cst is currently only implemented for rust".to_owned()); }
+    if self.incremental { self.check_incremental(g); }
+    if self.glr {
+      return match self.lang {
+        Lang::Rs => self.rs_glr(&g, &table, dfa),
+        _ => self.grammar_error("glr is currently only implemented for rust".to_owned()),
+      };
+    }
     match self.lang {
       Lang::Rs => self.rs_lalr1(&g, &table, dfa),
       Lang::Cpp => self.cpp_lalr1(&g, &table, dfa),
@@ -95,8 +188,12 @@ pub fn work(mut raw: RawGrammar, algo: PGAlgo, gen: &mut impl Codegen) -> Result
       let lr1 = if algo == LALR1 { lalr1_by_lr0::work(lr0::work(g), g) } else { lr1::work(g) };
       let orig_table = mk_table::mk_table(&lr1, g);
       let mut table = orig_table.clone();
-      let conflict = lalr1_core::mk_table::solve(&mut table, g);
-      gen.lr1(g, &lr1, &dfa, orig_table, table, conflict)
+      let (mut conflict, used) = lalr1_core::mk_table::solve(&mut table, g);
+      // canonical lr1 is only computed when there are reduce-reduce conflicts that may be caused by lalr1
+      if algo == LALR1 && conflict.iter().any(|c| c.resolved.is_none() && matches!(c.kind, ConflictKind::RR { .. })) {
+        lalr1_core::mk_table::mark_lalr_only(&mut conflict, &lr1, &lr1::work(g));
+      }
+      gen.lr1(g, &lr1, &dfa, orig_table, table, conflict, used)
     }
   }
 }
\ No newline at end of file
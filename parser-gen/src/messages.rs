@@ -0,0 +1,176 @@
+// custom error messages of lr states, read from a messages file like Menhir's `.messages`:
+// the file consists of entries separated by blank lines, each entry has one or more sentences (one per line), a blank line, and the message (can have multiple lines)
+// a sentence is a list of terminal names separated by whitespace, the parser must detect an error at its last token, which can be `_Eof`
+// lines starting with `#` in sentences are comments
+//
+// # 1 + ;
+// IntLit Add Semi
+// IntLit Sub Semi
+//
+// expected an integer after the operator
+use crate::*;
+
+pub struct Entry<'a> {
+  // (line number, sentence)
+  pub sentences: Vec<(usize, Vec<&'a str>)>,
+  pub message: String,
+}
+
+pub fn parse(text: &str) -> Vec<Entry<'_>> {
+  let mut ret = Vec::new();
+  let mut lines = text.lines().enumerate().map(|(i, l)| (i + 1, l.trim_end())).peekable();
+  loop {
+    let mut sentences = Vec::new();
+    while let Some(&(i, l)) = lines.peek() {
+      if l.is_empty() && !sentences.is_empty() { break; }
+      lines.next();
+      if !l.is_empty() && !l.starts_with('#') { sentences.push((i, l.split_whitespace().collect())); }
+    }
+    if sentences.is_empty() { break ret; }
+    while lines.peek().map_or(false, |&(_, l)| l.is_empty()) { lines.next(); }
+    let mut message = Vec::new();
+    while let Some((_, l)) = lines.next() {
+      if l.is_empty() { break; }
+      message.push(l);
+    }
+    ret.push(Entry { sentences, message: message.join("\n") });
+  }
+}
+
+// perform the reductions on lookahead `t`, return the next action that is not a reduction, None means error (detected in the top state of `stk`)
+// the first action is used for unsolved conflicts, the same as the generated parser
+fn reduce_all(g: &Grammar, table: &Table, stk: &mut Vec<u32>, t: u32) -> Option<Act> {
+  loop {
+    match sorted_get(&table[*stk.last().unwrap() as usize].act, t).and_then(|x| x.get(0)) {
+      Some(&Act::Reduce(p)) => {
+        let prod = &g.prod[p as usize];
+        stk.truncate(stk.len() - prod.rhs.len());
+        stk.push(*sorted_get(&table[*stk.last().unwrap() as usize].goto, prod.lhs + g.terms.len() as u32).unwrap());
+      }
+      act => return act.copied(),
+    }
+  }
+}
+
+// run the lr automaton on `sentence` (terminal ids), return the state where the error is detected, or None if there is no error at the last token
+pub fn error_state(g: &Grammar, table: &Table, sentence: &[u32]) -> Option<u32> {
+  let mut stk = vec![0];
+  for (i, &t) in sentence.iter().enumerate() {
+    match reduce_all(g, table, &mut stk, t) {
+      Some(Act::Shift(s)) => stk.push(s),
+      Some(_) => return None,
+      None => return if i + 1 == sentence.len() { stk.last().copied() } else { None },
+    }
+  }
+  None
+}
+
+// the message of each state, read from the messages file `text`
+pub fn compile(g: &Grammar, table: &Table, text: &str) -> std::result::Result<Vec<Option<String>>, String> {
+  let terms2id = g.terms.iter().enumerate().map(|(i, t)| (t.name, i as u32)).collect::<HashMap<_, _>>();
+  let mut ret = vec![None; table.len()];
+  // the line of the sentence that defines the message of each state
+  let mut defined = HashMap::default();
+  for Entry { sentences, message } in parse(text) {
+    for (line, sentence) in sentences {
+      let sentence = sentence.iter().map(|&t| terms2id.get(t).copied().filter(|&t| t != EPS_IDX as u32)
+        .ok_or_else(|| format!("line {}: \"{}\" is not a terminal", line, t))).collect::<std::result::Result<Vec<_>, _>>()?;
+      let state = error_state(g, table, &sentence).ok_or_else(|| format!("line {}: the sentence doesn't cause an error at its last token", line))?;
+      if let Some(old) = defined.insert(state, line) {
+        if ret[state as usize].as_ref() != Some(&message) {
+          return Err(format!("line {}: the sentence leads to state {}, which already has a different message at line {}", line, state, old));
+        }
+      }
+      ret[state as usize] = Some(message.clone());
+    }
+  }
+  Ok(ret)
+}
+
+// the shortest terminal string that each non-terminal derives, None if it derives no terminal string
+fn shortest_derive(g: &Grammar) -> Vec<Option<Vec<u32>>> {
+  let mut ret: Vec<Option<Vec<u32>>> = vec![None; g.nt.len()];
+  let mut changed = true;
+  while changed {
+    changed = false;
+    'prod: for p in &g.prod {
+      let mut s = Vec::new();
+      for &x in p.rhs.iter() {
+        match g.as_nt(x) {
+          Some(nt) => match &ret[nt] { Some(d) => s.extend_from_slice(d), None => continue 'prod }
+          None => s.push(x),
+        }
+      }
+      if ret[p.lhs as usize].as_ref().map_or(true, |old| s.len() < old.len()) {
+        ret[p.lhs as usize] = Some(s);
+        changed = true;
+      }
+    }
+  }
+  ret
+}
+
+// find a shortest sentence for each state where an error can be detected, sorted by state, like Menhir's `--list-errors`:
+// the shortest path of symbols from state 0 to each state is found by dijkstra, where a terminal weighs 1 and a non-terminal weighs the length of
+// its shortest derived terminal string, then each non-terminal on the path is replaced by that string, and a terminal that the state can't accept is appended
+// the sentence is checked by running the parser on it, because the reductions that lead to the state may need other lookahead tokens
+pub fn samples(g: &Grammar, table: &Table) -> Vec<(u32, Vec<u32>)> {
+  use std::{cmp::Reverse, collections::BinaryHeap};
+  let derive = shortest_derive(g);
+  // (distance, previous state, symbol on the edge)
+  let mut path = vec![(usize::MAX, 0, 0); table.len()];
+  path[0].0 = 0;
+  let mut q = BinaryHeap::new();
+  q.push(Reverse((0, 0u32)));
+  while let Some(Reverse((d, s))) = q.pop() {
+    if d > path[s as usize].0 { continue; }
+    let entry = &table[s as usize];
+    // the first action of an unsolved conflict is used, the same as the generated parser
+    let shift = entry.act.iter().filter_map(|(t, acts)| match acts.get(0) { Some(&Act::Shift(to)) => Some((*t, to, 1)), _ => None });
+    let goto = entry.goto.iter().filter_map(|&(nt, to)| Some((nt, to, derive[g.as_nt(nt)?].as_ref()?.len())));
+    for (sym, to, w) in shift.chain(goto) {
+      if d + w < path[to as usize].0 {
+        path[to as usize] = (d + w, s, sym);
+        q.push(Reverse((d + w, to)));
+      }
+    }
+  }
+  let mut ret = Vec::new();
+  for s in 0..table.len() as u32 {
+    if path[s as usize].0 == usize::MAX { continue; }
+    let (mut syms, mut cur) = (Vec::new(), s);
+    while cur != 0 {
+      let (_, prev, sym) = path[cur as usize];
+      syms.push(sym);
+      cur = prev;
+    }
+    let mut sentence = Vec::new();
+    for &x in syms.iter().rev() {
+      match g.as_nt(x) { Some(nt) => sentence.extend_from_slice(derive[nt].as_ref().unwrap()), None => sentence.push(x) }
+    }
+    // `_Eps` and `_Err` never appear in the input
+    for t in EOF_IDX as u32..g.terms.len() as u32 {
+      if t == ERR_IDX as u32 || sorted_get(&table[s as usize].act, t).map_or(false, |x| !x.is_empty()) { continue; }
+      sentence.push(t);
+      if error_state(g, table, &sentence) == Some(s) {
+        ret.push((s, sentence));
+        break;
+      }
+      sentence.pop();
+    }
+  }
+  ret
+}
+
+// list the error states that have no message in `messages` (None means no messages file), in the format of the messages file
+pub fn uncovered<'a>(g: &'a Grammar, table: &'a Table, messages: Option<&'a [Option<String>]>) -> impl Display + 'a {
+  fmt_::fn2display(move |f| {
+    for (state, sentence) in samples(g, table) {
+      if messages.map_or(false, |m| m[state as usize].is_some()) { continue; }
+      writeln!(f, "# state {}", state)?;
+      writeln!(f, "{}\n", sentence.iter().map(|&t| g.show_token(t as _)).collect::<Vec<_>>().join(" "))?;
+      writeln!(f, "<YOUR SYNTAX ERROR MESSAGE HERE>\n")?;
+    }
+    Ok(())
+  })
+}
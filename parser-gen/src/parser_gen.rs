@@ -1,39 +1,99 @@
 use clap::{App, Arg};
 use std::{io, fs};
 use parser_gen::*;
+use common::RawGrammar;
+
+fn reject(msg: &str) -> ! {
+  eprintln!("error: {}", msg);
+  std::process::exit(1)
+}
 
 fn main() -> io::Result<()> {
   let m = App::new("parser_gen")
     .author("MashPlant").about("Read config from a toml file, and generate a parser in various language")
     .arg(Arg::new("input").required(true))
-    .arg(Arg::new("output").long("output").short('o').takes_value(true).required(true).value_name("path"))
-    .arg(Arg::new("lang").long("lang").short('l').takes_value(true).possible_values(&["rs", "cpp", "java"]).required(true))
+    .arg(Arg::new("output").long("output").short('o').takes_value(true).required_unless_present_any(&["explain", "list_errors"]).value_name("path"))
+    .arg(Arg::new("lang").long("lang").short('l').takes_value(true).possible_values(&["rs", "cpp", "java"]).required_unless_present_any(&["explain", "list_errors"]))
     .arg(Arg::new("verbose").long("verbose").takes_value(true).value_name("path").help("Print some parser information (ll table or lr fsm) to the path"))
     .arg(Arg::new("show_fsm").long("show_fsm").takes_value(true).value_name("path").help("Print lr fsm in dot file format to the path"))
     .arg(Arg::new("show_dfa").long("show_dfa").takes_value(true).value_name("path").help("Print dfa in dot file format to the path"))
     .arg(Arg::new("log_token").long("log_token").help("Make parser print recognized token"))
     .arg(Arg::new("log_reduce").long("log_reduce").help("Make parser print the rule used when reducing"))
     .arg(Arg::new("use_unsafe").long("use_unsafe").help("Make parser use some unsafe operations to improve speed"))
+    .arg(Arg::new("glr").long("glr").help("Generate a GLR parser, which tries all actions of unsolved conflicts"))
+    .arg(Arg::new("ll1").long("ll1").conflicts_with("glr").help("Generate an LL(1) parser instead of an LALR(1) parser"))
+    .arg(Arg::new("ll_k").long("ll_k").takes_value(true).value_name("k").requires("ll1")
+      .help("Solve the conflicts in LL(1) table by at most k tokens of lookahead"))
+    .arg(Arg::new("user_parse").long("user_parse").requires("ll1").help("Don't generate `_parse` for the LL(1) rust parser, the user should implement it"))
+    .arg(Arg::new("explain").long("explain").takes_value(true).value_name("state,item,token")
+      .help("Explain why the token is in the lookahead of the item (index in the state's item list in verbose output) of the lalr1 state, instead of generating code"))
+    .arg(Arg::new("messages").long("messages").takes_value(true).value_name("path").conflicts_with_all(&["glr", "ll1"])
+      .help("Read custom error messages of lalr1 states from the messages file, only for rust"))
+    .arg(Arg::new("repair").long("repair").conflicts_with_all(&["glr", "ll1"])
+      .help("Make the rust lalr1 parser repair syntax errors by inserting, deleting or replacing a few tokens"))
+    .arg(Arg::new("stream").long("stream")
+      .help("Also generate a stream lexer, which reads the input from `std::io::Read` (rs), `std::istream` (cpp) or `InputStream` (java)"))
+    .arg(Arg::new("incremental").long("incremental").conflicts_with_all(&["glr", "ll1"])
+      .help("Also generate `Incremental` for the rust lalr1 parser, which reparses the text after edits by reusing the unaffected part of the old tree"))
+    .arg(Arg::new("cst").long("cst")
+      .help("Ignore the types and actions of productions, make the rust parser build a concrete syntax tree instead"))
+    .arg(Arg::new("list_errors").long("list_errors").conflicts_with_all(&["explain", "ll1"])
+      .help("Print a sample sentence for each lalr1 error state not covered by the messages file, in its format, instead of generating code"))
     .get_matches();
+  let input = fs::read_to_string(m.value_of("input").unwrap())?;
+  let mut raw: RawGrammar = toml::from_str(&input).expect("invalid grammar toml");
+  if let Some(explain) = m.value_of("explain") {
+    let (state, item, token) = match explain.split(',').collect::<Vec<_>>().as_slice() {
+      &[state, item, token] => (state.parse().ok(), item.parse().ok(), token),
+      _ => (None, None, ""),
+    };
+    let (state, item) = state.zip(item).expect("explain should be in the form of \"state,item,token\"");
+    let ref g = raw.extend(true).expect("invalid grammar");
+    let lr1 = lalr1_core::lalr1_by_lr0::work(lalr1_core::lr0::work(g), g);
+    print!("{}", show_lr::explain_token(g, &lr1, state, item, token));
+    return Ok(());
+  }
+  if m.is_present("list_errors") {
+    let ref g = raw.extend(true).expect("invalid grammar");
+    let lr1 = lalr1_core::lalr1_by_lr0::work(lalr1_core::lr0::work(g), g);
+    let mut table = lalr1_core::mk_table::mk_table(&lr1, g);
+    lalr1_core::mk_table::solve(&mut table, g);
+    let messages = match m.value_of("messages") {
+      Some(path) => Some(messages::compile(g, &table, &fs::read_to_string(path)?).expect("invalid messages file")),
+      None => None,
+    };
+    print!("{}", messages::uncovered(g, &table, messages.as_deref()));
+    return Ok(());
+  }
+  let lang = match m.value_of("lang") {
+    Some("rs") => Lang::Rs, Some("cpp") => Lang::Cpp, Some("java") => Lang::Java,
+    _ => unreachable!()
+  };
+  // these options are only implemented for rust, reject them before generating anything
+  if lang != Lang::Rs {
+    for arg in ["glr", "messages", "repair", "incremental", "cst"] {
+      if m.is_present(arg) { reject(&format!("`--{}` is only supported for `--lang rs`", arg)); }
+    }
+    if m.is_present("ll1") && !raw.pratt.is_empty() { reject("`pratt` in the grammar is only supported for `--lang rs`"); }
+  }
   let output = fs::File::create(m.value_of("output").unwrap())
     .expect("failed to open output file");
   let output = io::BufWriter::new(output);
-  let mut cfg = Config {
-    verbose: m.value_of("verbose"),
-    show_fsm: m.value_of("show_fsm"),
-    show_dfa: m.value_of("show_dfa"),
-    log_token: m.is_present("log_token"),
-    log_reduce: m.is_present("log_reduce"),
-    use_unsafe: m.is_present("use_unsafe"),
-    lang: match m.value_of("lang") {
-      Some("rs") => Lang::Rs, Some("cpp") => Lang::Cpp, Some("java") => Lang::Java,
-      _ => unreachable!()
-    },
-    on_conflict: |c| eprintln!("{}", c),
-    code_output: output,
-  };
-  let input = fs::read_to_string(m.value_of("input").unwrap())?;
-  let raw = toml::from_str(&input).expect("invalid grammar toml");
-  work(raw, PGAlgo::LALR1, &mut cfg).expect("failed to generate code");
+  let mut cfg = Config::new(lang, output);
+  cfg.verbose = m.value_of("verbose");
+  cfg.show_fsm = m.value_of("show_fsm");
+  cfg.show_dfa = m.value_of("show_dfa");
+  cfg.log_token = m.is_present("log_token");
+  cfg.log_reduce = m.is_present("log_reduce");
+  cfg.use_unsafe = m.is_present("use_unsafe");
+  cfg.glr = m.is_present("glr");
+  cfg.user_parse = m.is_present("user_parse");
+  cfg.ll_k = m.value_of("ll_k").map(|k| k.parse().expect("k should be an integer")).unwrap_or(1);
+  cfg.messages = m.value_of("messages");
+  cfg.repair = m.is_present("repair");
+  cfg.stream = m.is_present("stream");
+  cfg.incremental = m.is_present("incremental");
+  cfg.cst = m.is_present("cst");
+  work(raw, if m.is_present("ll1") { PGAlgo::LL1 } else { PGAlgo::LALR1 }, &mut cfg).expect("failed to generate code");
   Ok(())
 }
\ No newline at end of file
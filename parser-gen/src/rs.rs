@@ -38,12 +38,37 @@ impl<W: std::io::Write> Config<'_, W> {
       parser_struct = fmt_::fn2display(move |f| if g.raw.parser_def.is_none() {
         write!(f, "pub struct Parser{{{}}}", g.raw.parser_field)
       } else { Ok(()) })
-    )
+    )?;
+    if self.stream { write!(self.code_output, include_str!("template/stream.rs.template"))?; }
+    if self.cst {
+      let user_nt = &g.nt[..g.nt.len() - 1]; // exclude `_`
+      write!(
+        self.code_output, include_str!("template/cst.rs.template"),
+        nt_kind = fmt::comma_sep(user_nt.iter().map(|x| x.name)),
+        prod_size = g.prod.len(),
+        cst_prod = fmt::comma_sep((0..g.prod.len()).map(|i| format!("{:?}", g.show_prod(i, None).to_string()))),
+      )?;
+    }
+    Ok(())
+  }
+
+  // return (types, types2id, the type of the parse result), in cst mode, all non-terminals have type `Node`
+  fn rs_types<'a>(&self, g: &Grammar<'a>) -> (Vec<&'a str>, HashMap<&'a str, u32>, &'a str) {
+    if self.cst {
+      let mut types2id = HashMap::default();
+      for nt in &g.nt { types2id.insert(nt.ty, 0); }
+      types2id.insert("Node<'p>", 0);
+      (vec!["Node<'p>"], types2id, "Node<'p>")
+    } else {
+      let (types, types2id) = fmt::gather_types(g);
+      (types, types2id, g.nt.last().unwrap().ty)
+    }
   }
 
-  // log_reduce == self.log_reduce, but this functions cannot borrow self
+  // log_reduce == self.log_reduce, cst == self.cst, but this functions cannot borrow self
   // is_pair == true: `stk` is Vec<(StackItem, integer)>; is_pair == false: `stk` is Vec<StackItem>
-  fn gen_act<'a>(log_reduce: bool, g: &'a Grammar, types2id: HashMap<&'a str, u32>, is_pair: bool, handle_err: &'a str) -> impl std::fmt::Display + 'a {
+  // cst == true: the action of a user production builds a `Node`, and the values are named `_1`, `_2`, ... even if `args` is provided
+  fn gen_act<'a>(log_reduce: bool, cst: bool, g: &'a Grammar, types2id: HashMap<&'a str, u32>, is_pair: bool, handle_err: &'a str) -> impl std::fmt::Display + 'a {
     fmt_::fn2display(move |f| {
       let pat = if is_pair { ",_" } else { "" };
       for (i, prod) in g.prod.iter().enumerate() {
@@ -53,7 +78,7 @@ impl<W: std::io::Write> Config<'_, W> {
         }
         for (j, &x) in prod.rhs.iter().enumerate().rev() {
           let name = fmt_::fn2display(move |f|
-            match prod.args { Some(args) => f.write_str(args[j].0), None => write!(f, "_{}", j + 1) });
+            match prod.args { Some(args) if !cst => f.write_str(args[j].0), _ => write!(f, "_{}", j + 1) });
           if let Some(x) = g.as_nt(x) {
             let id = types2id[g.nt[x].ty];
             writeln!(f, "let {}=match stk.pop(){{Some((StackItem::_{}(x){}))=>x,_=>{}}};", name, id, pat, handle_err)?;
@@ -62,7 +87,14 @@ impl<W: std::io::Write> Config<'_, W> {
           }
         }
         let id = types2id[g.nt[prod.lhs as usize].ty];
-        writeln!(f, "StackItem::_{}({{{}}})}}", id, prod.act)?;
+        // the action of `_ -> Start` is always `_1`
+        if cst && (prod.lhs as usize) < g.nt.len() - 1 {
+          write!(f, "StackItem::_{}(Node{{kind:NonTermKind::{},prod:{},children:vec![", id, g.nt[prod.lhs as usize].name, i)?;
+          for j in 0..prod.rhs.len() { write!(f, "_{}.into(),", j + 1)?; }
+          writeln!(f, "]}})}}")?;
+        } else {
+          writeln!(f, "StackItem::_{}({{{}}})}}", id, prod.act)?;
+        }
       }
       Ok(())
     })
@@ -72,16 +104,35 @@ impl<W: std::io::Write> Config<'_, W> {
 impl<W: std::io::Write> Config<'_, W> {
   // return None if `rs_common` returns None, you can check the doc of `rs_common`
   pub fn rs_lalr1(&mut self, g: &Grammar, table: &Table, dfa: &Dfa) -> Result<()> {
-    let (types, types2id) = fmt::gather_types(g);
-    let parse_res = g.nt.last().unwrap().ty;
+    let (types, types2id, parse_res) = self.rs_types(g);
     let res_id = types2id[parse_res];
+    let messages = match self.messages {
+      Some(path) => messages::compile(g, table, &std::fs::read_to_string(path)?)
+        .unwrap_or_else(|e| panic!("invalid messages file \"{}\", reason: {}", path, e)),
+      None => vec![None; table.len()],
+    };
+    // with `error` productions, the recovered syntax errors are also returned
+    let recover = g.prod.iter().any(|p| p.rhs.contains(&(ERR_IDX as u32)));
     self.rs_common(g, dfa, &types, false)?;
+    write!(self.code_output, include_str!("template/parse_error.rs.template"))?;
+    if self.repair {
+      write!(
+        self.code_output, include_str!("template/repair.rs.template"),
+        term_num = g.terms.len(),
+        terms = fmt::comma_sep(g.terms.iter().map(|x| format!("TokenKind::{}", x.name))),
+        prod_size = g.prod.len(),
+        prod_len = fmt::comma_sep(g.prod.iter().map(|x| x.rhs.len())),
+      )?;
+    }
     write!(
       self.code_output, include_str!("template/lalr1.rs.template"),
       u_lr_fsm_size = fmt::min_u(table.len()),
       parser_type = g.raw.parser_def.unwrap_or("Parser"),
       res_type = parse_res,
       res_id = res_id,
+      ok_type = if self.repair { format!("({}, Vec<ParseError<'l>>, Vec<Repair<'l>>)", parse_res) }
+        else if recover { format!("({}, Vec<ParseError<'l>>)", parse_res) } else { parse_res.to_owned() },
+      accept = if self.repair { "(r, errors, repairs)" } else if recover { "(r, errors)" } else { "r" },
       prod_size = g.prod.len(),
       prod = fmt::comma_sep(g.prod.iter().map(|x| x.lhs)),
       term_num = g.terms.len(),
@@ -90,14 +141,94 @@ impl<W: std::io::Write> Config<'_, W> {
       u_act_size = fmt::min_u(table.len() * 4),
       action = fmt::action(g, table, ('[', ']')),
       goto = fmt::goto(g, &table, ('[', ']')),
-      parser_act = Self::gen_act(self.log_reduce, g, types2id, true, "err!()"),
+      expected = fmt::expected(g, table),
+      // `Option<String>`'s debug output is a valid `Option<&str>` literal
+      message = fmt::comma_sep(messages.into_iter().map(|m| format!("{:?}", m))),
+      parser_act = Self::gen_act(self.log_reduce, self.cst, g, types2id, true, "err!()"),
+      log_token = if self.log_token { r#"println!("{:?}",token);"# } else { "" },
+      repair_init = if self.repair { "let (mut buf, mut repairs) = (std::collections::VecDeque::new(), Vec::new());" } else { "" },
+      next_token = if self.repair { "buf.pop_front().unwrap_or_else(|| lexer.next())" } else { "lexer.next()" },
+      repair = if self.repair {
+        r"if err_status == 0 {
+            let stk = stk.iter().map(|x| x.1 as usize).collect();
+            if let Some(t) = _repair(stk, token, &mut buf, lexer, &mut repairs, Self::_token_action, |s, prod| Self::_goto(s, prod) as usize) {
+              errors.push(Self::_parse_error(token, state));
+              token = t;
+              continue;
+            }
+          }"
+      } else { "" },
+    )?;
+    // `parse_stream` drops the tokens after reducing them, so the values of non-terminals can't borrow from them
+    let borrow = if self.cst { Some("in cst mode, `Node` holds the tokens".to_owned()) } else {
+      g.nt.iter().find(|x| fmt::has_lifetime(x.ty, "'p")).map(|x| format!("the type `{}` of non-term \"{}\" has lifetime `'p`", x.ty, x.name))
+    };
+    if let (true, Some(reason)) = (self.stream, &borrow) {
+      (self.on_conflict)(format!("`parse_stream` is not generated, because the values of non-terminals borrow from tokens: {}", reason));
+    }
+    if self.stream && borrow.is_none() {
+      write!(
+        self.code_output, include_str!("template/stream_parse.rs.template"),
+        prod_size = g.prod.len(),
+        u_prod_len = fmt::min_u(g.prod.iter().map(|x| x.rhs.len()).max().unwrap_or(0)),
+        prod_len = fmt::comma_sep(g.prod.iter().map(|x| x.rhs.len())),
+        rebind = (0..types.len()).map(|i| format!("StackItem::_{0}(x) => StackItem::_{0}(x),", i)).collect::<String>(),
+        parser_type = g.raw.parser_def.unwrap_or("Parser"),
+        ok_type = if recover { format!("({}, Vec<std::io::Error>)", parse_res) } else { parse_res.to_owned() },
+        accept = if recover { "(r, errors)" } else { "r" },
+        res_id = res_id,
+        u_lr_fsm_size = fmt::min_u(table.len()),
+        log_token = if self.log_token { r#"println!("{:?}",token);"# } else { "" },
+      )?;
+    }
+    if self.incremental {
+      write!(
+        self.code_output, include_str!("template/incremental.rs.template"),
+        parser_type = g.raw.parser_def.unwrap_or("Parser"),
+        prod_size = g.prod.len(),
+        u_prod_len = fmt::min_u(g.prod.iter().map(|x| x.rhs.len()).max().unwrap_or(0)),
+        prod_len = fmt::comma_sep(g.prod.iter().map(|x| x.rhs.len())),
+      )?;
+    }
+    Ok(())
+  }
+
+  pub fn rs_glr(&mut self, g: &Grammar, table: &Table, dfa: &Dfa) -> Result<()> {
+    let (types, types2id, parse_res) = self.rs_types(g);
+    let res_id = types2id[parse_res];
+    let multi_act_num = fmt::multi_act_num(table);
+    self.rs_common(g, dfa, &types, false)?;
+    write!(self.code_output, include_str!("template/parse_error.rs.template"))?;
+    write!(
+      self.code_output, include_str!("template/glr.rs.template"),
+      u_lr_fsm_size = fmt::min_u(table.len()),
+      parser_type = g.raw.parser_def.unwrap_or("Parser"),
+      res_type = parse_res,
+      res_id = res_id,
+      prod_size = g.prod.len(),
+      prod = fmt::comma_sep(g.prod.iter().map(|x| x.lhs)),
+      u_prod_len = fmt::min_u(g.prod.iter().map(|x| x.rhs.len()).max().unwrap_or(0)),
+      prod_len = fmt::comma_sep(g.prod.iter().map(|x| x.rhs.len())),
+      term_num = g.terms.len(),
+      nt_num = g.nt.len(),
+      lr_fsm_size = table.len(),
+      u_act_size = fmt::min_u(table.len().max(g.prod.len()).max(multi_act_num + 1) * 4),
+      action = fmt::glr_action(g, table),
+      multi_act_num = multi_act_num,
+      multi_act = fmt::multi_act(table),
+      goto = fmt::goto(g, &table, ('[', ']')),
+      expected = fmt::expected(g, table),
+      // without unsolved conflicts there is only one stack, and a symbol never has more than one alternative
+      merge = if multi_act_num > 0 {
+        format!("Some(old) => self._merge(*idx!(PROD, *prod as usize) as u32 + {}, old, value),", g.terms.len())
+      } else { "Some(_) => err!(),".to_owned() },
+      parser_act = Self::gen_act(self.log_reduce, self.cst, g, types2id, false, "err!()"),
       log_token = if self.log_token { r#"println!("{:?}",token);"# } else { "" },
     )
   }
 
-  pub fn rs_ll1(&mut self, g: &Grammar, ll: &LLCtx, dfa: &Dfa) -> Result<()> {
-    let (types, types2id) = fmt::gather_types(g);
-    let parse_res = g.nt.last().unwrap().ty;
+  pub fn rs_ll1(&mut self, g: &Grammar, ll: &LLCtx, llk: &[LLkEntry], dfa: &Dfa) -> Result<()> {
+    let (types, types2id, parse_res) = self.rs_types(g);
     let res_id = types2id[parse_res];
     self.rs_common(g, dfa, &types, true)?;
     write!(
@@ -111,17 +242,38 @@ impl<W: std::io::Write> Config<'_, W> {
       }, Ok(())).1),
       table = fmt_::fn2display(move |f| (for table in &ll.table {
         f.write_str("map!(")?;
-        for (&predict, prod_ids) in table {
+        for &(predict, ref prod_ids) in table {
           let prod_id = prod_ids[0] as usize;
           write!(f, "{}=>({},vec!{:?}),", predict, prod_id, g.prod[prod_id].rhs)?;
         }
         f.write_str("),\n")?;
       }, Ok(())).1),
       parser_type = g.raw.parser_def.unwrap_or("Parser"),
-      parser_act = Self::gen_act(self.log_reduce, g, types2id, false, "return StackItem::_Fail"),
+      parser_act = Self::gen_act(self.log_reduce, self.cst, g, types2id, false, "return StackItem::_Fail"),
+    )?;
+    if self.user_parse {
+      return write!(
+        self.code_output, include_str!("template/ll1_user_parse.rs.template"),
+        parser_type = g.raw.parser_def.unwrap_or("Parser"),
+        res_type = parse_res,
+        res_nt_id = g.token_num() - 1,
+        res_id = res_id
+      );
+    }
+    write!(
+      self.code_output, include_str!("template/ll1_parse.rs.template"),
       res_type = parse_res,
       res_nt_id = g.token_num() - 1,
-      res_id = res_id
+      res_id = res_id,
+      ll_k_num = llk.len(),
+      ll_k = fmt::ll_k_rs(llk),
+      prod_size = g.prod.len(),
+      prod_rhs = fmt::comma_sep(g.prod.iter().map(|x| format!("&{:?}", x.rhs))),
+      nt_num = g.nt.len(),
+      pratt_op = fmt::pratt_op(g),
+      pratt_prefix_len = g.pratt.iter().map(|x| x.prefix.len()).sum::<usize>(),
+      pratt_prefix = fmt::pratt_prefix(g),
+      parser_type = g.raw.parser_def.unwrap_or("Parser"),
     )
   }
 }
\ No newline at end of file
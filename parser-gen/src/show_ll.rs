@@ -11,11 +11,15 @@ pub fn show_prod_token<'a>(g: &'a Grammar) -> impl Display + 'a {
   })
 }
 
-pub fn table<'a>(ll: &'a LLCtx, g: &'a Grammar) -> impl Display + 'a {
+fn find_llk(llk: &[LLkEntry], nt: usize, term: u32) -> Option<&LLkEntry> {
+  llk.binary_search_by_key(&(nt as u32, term), |x| (x.nt, x.term)).ok().map(|i| &llk[i])
+}
+
+pub fn table<'a>(ll: &'a LLCtx, llk: &'a [LLkEntry], g: &'a Grammar) -> impl Display + 'a {
   fmt_::fn2display(move |f| {
     write!(f, "{}", show_prod_token(g))?;
     for (idx, t) in ll.table.iter().enumerate() {
-      writeln!(f, "{}:", g.show_token(idx))?;
+      writeln!(f, "{}:", g.nt[idx].name)?;
       let mut show_set = |name: &str, set: &[u32]| {
         write!(f, "{}:", name)?;
         bitset::ibs(set).ones(|i| { let _ = write!(f, " {}", g.show_token(i)); });
@@ -24,28 +28,39 @@ pub fn table<'a>(ll: &'a LLCtx, g: &'a Grammar) -> impl Display + 'a {
       };
       show_set("first", &ll.first.get(idx))?;
       show_set("follow", &ll.follow.get(idx))?;
-      // this is not necessary, but sorting it will provide better readability
-      let mut t = t.iter().map(|(ch, prod)| (*ch, prod)).collect::<Vec<_>>();
-      t.sort_unstable_by_key(|x| x.0);
-      for (ch, prod) in t {
+      for &(ch, ref prod) in t {
         write!(f, "  {} => ", g.show_token(ch as _))?;
+        if let Some(e) = find_llk(llk, idx, ch) {
+          writeln!(f, "LL({}):", e.k)?;
+          for (s, prod) in &e.alt {
+            let s = fmt_::sep(s.iter().map(|&x| g.show_token(x as _)), " ");
+            writeln!(f, "    {} {} => {}", g.show_token(ch as _), s, g.show_prod(*prod as _, None))?;
+          }
+          continue;
+        }
         for (idx, &prod) in prod.iter().enumerate() {
           let prod = g.show_prod(prod as _, None);
           if idx == 0 { write!(f, "{}", prod)?; } else { write!(f, "; {}(✗)", prod)?; }
         }
         f.write_str("\n")?;
       }
+      if let Some(p) = g.pratt.iter().find(|x| x.nt == idx as u32) {
+        for x in p.infix.iter().chain(p.postfix.iter()) {
+          writeln!(f, "  {} => {} (pratt, priority {})", g.show_token(x.op as _), g.show_prod(x.prod as _, None), x.pri)?;
+        }
+      }
       f.write_str("\n")?;
     }
     Ok(())
   })
 }
 
-pub fn conflict(table: &LLTable, g: &Grammar) -> Vec<String> {
+// conflicts solved by `llk` are not reported
+pub fn conflict(table: &LLTable, llk: &[LLkEntry], g: &Grammar) -> Vec<String> {
   let mut ret = Vec::new();
-  for entry in table {
-    for (&predict, prod_ids) in entry {
-      if prod_ids.len() > 1 {
+  for (nt, entry) in table.iter().enumerate() {
+    for &(predict, ref prod_ids) in entry {
+      if prod_ids.len() > 1 && find_llk(llk, nt, predict).is_none() {
         let first_prod = g.show_prod(prod_ids[0] as _, None);
         for &other in prod_ids.iter().skip(1) {
           ret.push(format!("conflict at prod \"{}\" and \"{}\", both's PS contains \"{}\"",
@@ -55,4 +70,55 @@ pub fn conflict(table: &LLTable, g: &Grammar) -> Vec<String> {
     }
   }
   ret
-}
\ No newline at end of file
+}
+
+// show the result of `ll1_core::explain_follow`
+pub fn explain_follow<'a>(g: &'a Grammar, token: u32, chain: &'a [(u32, FollowReason)]) -> impl Display + 'a {
+  fmt_::fn2display(move |f| {
+    let tk = g.show_token(token as _);
+    for &(nt, r) in chain {
+      let name = g.nt[nt as usize].name;
+      match r {
+        FollowReason::Init => writeln!(f, "FOLLOW({}) contains \"{}\", because {} is the start non-terminal", name, tk, name)?,
+        FollowReason::First { prod, pos } | FollowReason::Lhs { prod, pos } => {
+          let rhs = &g.prod[prod as usize].rhs;
+          let beta = fmt_::sep(rhs[pos as usize + 1..].iter().map(|&x| g.show_token(x as _)), " ");
+          if let FollowReason::First { .. } = r {
+            writeln!(f, "FOLLOW({}) contains \"{}\", because in \"{}\", {} is followed by \"{}\", whose FIRST contains \"{}\"",
+              name, tk, g.show_prod(prod as _, None), name, beta, tk)?;
+          } else {
+            writeln!(f, "FOLLOW({}) contains FOLLOW({}), because in \"{}\", {} is followed by nullable \"{}\"",
+              name, g.nt[g.prod[prod as usize].lhs as usize].name, g.show_prod(prod as _, None), name, beta)?;
+          }
+        }
+      }
+    }
+    Ok(())
+  })
+}
+
+// show the result of `ll1_core::explain_first`
+pub fn explain_first<'a>(g: &'a Grammar, chain: &'a [(u32, u32)]) -> impl Display + 'a {
+  fmt_::fn2display(move |f| {
+    for &(prod, pos) in chain {
+      let p = &g.prod[prod as usize];
+      writeln!(f, "FIRST({}) contains FIRST({}), because in \"{}\", the symbols before it are nullable",
+        g.nt[p.lhs as usize].name, g.show_token(p.rhs[pos as usize] as _), g.show_prod(prod as _, None))?;
+    }
+    Ok(())
+  })
+}
+
+// explain why `token` (a terminal's name) is in FIRST and/or FOLLOW of `nt` (a non-terminal's name)
+pub fn explain_token(g: &Grammar, ll: &LLCtx, nt: &str, token: &str) -> String {
+  let (nt_id, t) = match (g.nt.iter().position(|x| x.name == nt), g.terms.iter().position(|x| x.name == token)) {
+    (Some(nt_id), Some(t)) => (nt_id, t as u32),
+    (None, _) => return format!("\"{}\" is not a non-terminal\n", nt),
+    (_, None) => return format!("\"{}\" is not a terminal\n", token),
+  };
+  let mut ret = String::new();
+  if let Some(chain) = ll1_core::explain_first(g, &ll.first, nt_id, t) { ret += &explain_first(g, &chain).to_string(); }
+  if let Some(chain) = ll1_core::explain_follow(g, &ll.first, &ll.follow, nt_id, t) { ret += &explain_follow(g, t, &chain).to_string(); }
+  if ret.is_empty() { ret = format!("\"{}\" is in neither FIRST({}) nor FOLLOW({})\n", token, nt, nt); }
+  ret
+}
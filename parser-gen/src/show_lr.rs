@@ -1,13 +1,13 @@
 use crate::*;
 
-pub fn table<'a>(orig_table: &'a Table, table: &'a Table, g: &'a Grammar) -> impl Display + 'a {
+pub fn table<'a>(orig_table: &'a Table, table: &'a Table, conflict: &'a [Conflict], g: &'a Grammar) -> impl Display + 'a {
   assert_eq!(orig_table.len(), table.len());
   fmt_::fn2display(move |f| {
     write!(f, "{}", show_ll::show_prod_token(g))?;
     for (idx, (o, n)) in orig_table.iter().zip(table.iter()).enumerate() {
       writeln!(f, "State {}:", idx)?;
-      for item in o.closure { // o and n have the same items
-        writeln!(f, "  {}", g.show_prod(item.prod_id as _, Some(item.dot)))?;
+      for (i, item) in o.closure.iter().enumerate() { // o and n have the same items
+        writeln!(f, "  {}: {}", i, g.show_prod(item.prod_id as _, Some(item.dot)))?;
       }
       f.write_str("\n")?;
       for ((ch, ao), (ch1, an)) in o.act.iter().zip(n.act.iter()) {
@@ -22,40 +22,95 @@ pub fn table<'a>(orig_table: &'a Table, table: &'a Table, g: &'a Grammar) -> imp
       }
       f.write_str("\n")?;
     }
+    let mut resolved = conflict.iter().filter_map(|c| match c.resolved { Some(Resolved::Directive(i)) => Some((c, i)), _ => None }).peekable();
+    if resolved.peek().is_some() { f.write_str("Resolved conflicts:\n")?; }
+    for (c, i) in resolved {
+      writeln!(f, "  {}", show_conflict(g, c))?;
+      writeln!(f, "    resolved by {}", show_resolve(g, &g.resolve[i as usize]))?;
+    }
     Ok(())
   })
 }
 
+pub fn show_resolve<'a>(g: &'a Grammar, r: &'a Resolve) -> impl Display + 'a {
+  fmt_::fn2display(move |f| {
+    write!(f, "resolve {{ token = \"{}\"", g.show_token(r.token as _))?;
+    if let Some(lhs) = r.lhs { write!(f, ", lhs = \"{}\"", g.nt[lhs as usize].name)?; }
+    match r.prefer {
+      ResolveAct::Shift => f.write_str(", prefer = \"shift\"")?,
+      ResolveAct::Reduce => f.write_str(", prefer = \"reduce\"")?,
+      ResolveAct::Prod(p) => write!(f, ", prefer = \"{}\"", g.show_prod(p as _, None))?,
+    }
+    if let Some(over) = r.over { write!(f, ", over = \"{}\"", g.show_prod(over as _, None))?; }
+    f.write_str(" }")
+  })
+}
+
+// only report conflicts that are not resolved by resolve directives
 pub fn conflict(g: &Grammar, c: &[Conflict]) -> Vec<String> {
-  let mut ret = Vec::new();
-  for c in c {
-    let ch = g.show_token(c.ch as _);
-    match c.kind {
-      ConflictKind::SR { s, r } =>
-        ret.push(format!("shift-reduce conflict at state {} when faced with token \"{}\", it can either shift {}, or reduce {}(\"{}\")",
-          c.state, ch, s, r, g.show_prod(r as _, None))),
-      ConflictKind::RR { r1, r2 } =>
-        ret.push(format!("reduce-reduce conflict at state {} when faced with token \"{}\", it can either reduce {}(\"{}\"), or reduce {}(\"{}\")",
-          c.state, ch, r1, g.show_prod(r1 as _, None), r2, g.show_prod(r2 as _, None))),
-      ConflictKind::Many(ref acts) => {
-        let mut msg = format!("Too many conflicts at state {} when faced with token \"{}\":\n", c.state, ch);
-        for a in acts {
-          match *a {
-            Act::Shift(s) => { let _ = write!(msg, "  - shift {}\n", s); }
-            Act::Reduce(r) => { let _ = write!(msg, "  - reduce {}('{}')\n", r, g.show_prod(r as _, None)); }
-            _ => unreachable!("there should be a bug in lr"),
-          }
+  c.iter().filter(|c| !matches!(c.resolved, Some(Resolved::Directive(_)))).map(|c| show_conflict(g, c)).collect()
+}
+
+// suggest `priority` rows for a shift-reduce conflict between reducing `r` and shifting `ch`, when one or both of them have no priority
+// the suggestion reproduces the current choice (shift) explicitly, and tells how to reduce instead
+fn suggest_priority(g: &Grammar, r: u32, ch: u32) -> Option<String> {
+  // a production without any terminal can only get priority from `prec`
+  let t = g.prod[r as usize].pri_term?;
+  let (tn, cn) = (g.show_token(t as _), g.show_token(ch as _));
+  Some(match (g.terms[t as usize].pri_assoc, g.terms[ch as usize].pri_assoc) {
+    (None, None) if t == ch =>
+      format!("add row `{{ assoc = 'right', terms = ['{0}'] }}` to `priority` to keep shifting, \
+        or row `{{ assoc = 'left', terms = ['{0}'] }}` to reduce instead (usual for a left associative binary operator)", tn),
+    (None, None) =>
+      format!("add rows `{{ assoc = 'left', terms = ['{}'] }}, {{ assoc = 'left', terms = ['{}'] }}` to `priority` (later rows have higher priority) \
+        to keep shifting, or swap them to reduce instead", tn, cn),
+    (Some((p, _)), None) =>
+      format!("\"{}\" has no priority, add it to a row after the row {} (0-based) of \"{}\" in `priority` to keep shifting, or before it to reduce instead", cn, p, tn),
+    (None, Some((p, _))) =>
+      format!("\"{}\" has no priority, add it to a row before the row {} (0-based) of \"{}\" in `priority` to keep shifting, or after it to reduce instead", tn, p, cn),
+    _ => return None,
+  })
+}
+
+fn show_conflict(g: &Grammar, c: &Conflict) -> String {
+  let ch = g.show_token(c.ch as _);
+  match c.kind {
+    ConflictKind::SR { s, r } => {
+      let mut msg = format!("shift-reduce conflict at state {} when faced with token \"{}\", it can either shift {}, or reduce {}(\"{}\")",
+        c.state, ch, s, r, g.show_prod(r as _, None));
+      if let Some(help) = suggest_priority(g, r, c.ch) { let _ = write!(msg, "\nhelp: {}", help); }
+      msg
+    }
+    ConflictKind::RR { r1, r2 } => {
+      let mut msg = format!("reduce-reduce conflict at state {} when faced with token \"{}\", it can either reduce {}(\"{}\"), or reduce {}(\"{}\")",
+        c.state, ch, r1, g.show_prod(r1 as _, None), r2, g.show_prod(r2 as _, None));
+      if let Some(merged) = &c.lalr_only {
+        msg += "\nthis conflict is LALR-only (mysterious), it doesn't exist in canonical lr1, and is introduced by merging these canonical lr1 states:\n";
+        for &(s, r) in merged { let _ = write!(msg, "  - state {}, which reduces {}(\"{}\")\n", s, r, g.show_prod(r as _, None)); }
+      }
+      msg
+    }
+    ConflictKind::Many(ref acts) => {
+      let mut msg = format!("{}-way conflict at state {} when faced with token \"{}\", {}:\n", acts.len(), c.state, ch, match c.resolved {
+        Some(Resolved::Priority(true)) => "it is solved by priority, the first one is chosen",
+        Some(Resolved::Priority(false)) => "it is solved by priority, all of them are removed, so it becomes an error",
+        _ => "the first one is chosen",
+      });
+      for a in acts {
+        match *a {
+          Act::Shift(s) => { let _ = write!(msg, "  - shift {}\n", s); }
+          Act::Reduce(r) => { let _ = write!(msg, "  - reduce {}(\"{}\")\n", r, g.show_prod(r as _, None)); }
+          Act::Acc => msg += "  - accept\n",
         }
-        ret.push(msg);
       }
+      msg
     }
   }
-  ret
 }
 
-fn show_link(g: &Grammar, link: &HashMap<u32, u32>, idx: usize, f: &mut Formatter) -> FmtResult {
-  let mut link = link.iter().map(|(&k, &v)| (k, v)).collect::<Vec<_>>();
-  link.sort_unstable_by_key(|kv| kv.1);
+fn show_link(g: &Grammar, link: &Link, idx: usize, f: &mut Formatter) -> FmtResult {
+  let mut link = link.clone();
+  link.sort_by_key(|kv| kv.1);
   for (k, v) in link { writeln!(f, r#"{} -> {} [label="{}"];"#, idx, v, g.show_token(k as _))?; }
   Ok(())
 }
@@ -96,4 +151,64 @@ pub fn lr1_dot<'a>(g: &'a Grammar, lr1: &'a Lr1Fsm) -> impl Display + 'a {
     }
     f.write_str("}")
   })
-}
\ No newline at end of file
+}
+
+// show the result of `lalr1_by_lr0::explain`
+pub fn explain<'a>(g: &'a Grammar, lr1: &'a Lr1Fsm, token: u32, chain: &'a [ExplainStep]) -> impl Display + 'a {
+  fmt_::fn2display(move |f| {
+    let tk = g.show_token(token as _);
+    let get = |s: u32, i: u32| &lr1[s as usize].closure[i as usize];
+    let show_item = |s: u32, i: u32| g.show_prod(get(s, i).prod_id as _, Some(get(s, i).dot));
+    for &(s, i, r) in chain {
+      write!(f, "state {}, item {} \"{}\": ", s, i, show_item(s, i))?;
+      match r {
+        LookaheadReason::Init => writeln!(f, "\"{}\" is the initial lookahead of the start item", tk)?,
+        LookaheadReason::Spontaneous { from } => {
+          let parent = get(s, from);
+          let beta = fmt_::sep(parent.prod[parent.dot as usize + 1..].iter().map(|&x| g.show_token(x as _)), " ");
+          writeln!(f, "generated by the closure of item {} \"{}\", \"{}\" is in FIRST(\"{}\")", from, show_item(s, from), tk, beta)?;
+        }
+        LookaheadReason::Closure => f.write_str("propagated by the closure of the previous item, the symbols after the non-terminal are nullable\n")?,
+        LookaheadReason::Goto => writeln!(f, "propagated from the previous item by goto on \"{}\"", g.show_token(get(s, i).prod[get(s, i).dot as usize - 1] as _))?,
+      }
+    }
+    Ok(())
+  })
+}
+
+// explain why `token` (a terminal's name) is in the lookahead of `lr1[state].closure[item]`
+pub fn explain_token(g: &Grammar, lr1: &Lr1Fsm, state: u32, item: u32, token: &str) -> String {
+  match g.terms.iter().position(|t| t.name == token) {
+    None => format!("\"{}\" is not a terminal\n", token),
+    Some(t) => match lalr1_by_lr0::explain(lr1, g, state, item, t as u32) {
+      Some(chain) => explain(g, lr1, t as u32, &chain).to_string(),
+      None => format!("\"{}\" is not in the lookahead of item {} of state {}\n", token, item, state),
+    }
+  }
+}
+
+// warnings about priority declarations that never decide a conflict (like bison's -Wprecedence)
+pub fn unused_priority(g: &Grammar, used: &PriUsed) -> Vec<String> {
+  let mut ret = Vec::new();
+  for (i, row) in g.raw.priority.iter().enumerate() {
+    let unused = g.terms.iter().enumerate()
+      .filter(|&(t, x)| x.pri_assoc.map(|x| x.0) == Some(i as u32) && !used.term[t]).map(|(_, x)| x.name).collect::<Vec<_>>();
+    if unused.is_empty() { continue; }
+    if unused.len() == row.terms.len() {
+      ret.push(format!("priority row {} ({}) never decides a conflict", i, fmt_::sep(unused.iter().map(|x| format!("\"{}\"", x)), ", ")));
+    } else {
+      for name in unused { ret.push(format!("priority of term \"{}\" (row {}) never decides a conflict", name, i)); }
+    }
+  }
+  for (i, p) in g.prod.iter().enumerate() {
+    if let (true, false, Some(t)) = (p.by_prec, used.prec[i], p.pri_term) {
+      let (tn, prod) = (g.show_token(t as _), g.show_prod(i, None));
+      ret.push(if g.terms[t as usize].pri_assoc.is_none() {
+        format!("prec \"{}\" of production \"{}\" is ineffective, because \"{}\" has no priority", tn, prod, tn)
+      } else {
+        format!("prec \"{}\" of production \"{}\" never decides a conflict", tn, prod)
+      });
+    }
+  }
+  ret
+}
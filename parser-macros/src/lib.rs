@@ -26,6 +26,14 @@ struct Config {
   #[darling(default)] log_token: bool,
   #[darling(default)] log_reduce: bool,
   #[darling(default)] use_unsafe: bool,
+  #[darling(default)] glr: bool,
+  #[darling(default)] user_parse: bool,
+  #[darling(default)] ll_k: u32,
+  #[darling(default)] messages: Option<String>,
+  #[darling(default)] repair: bool,
+  #[darling(default)] stream: bool,
+  #[darling(default)] incremental: bool,
+  #[darling(default)] cst: bool,
   #[darling(default)] expand: bool,
 }
 
@@ -34,6 +42,8 @@ struct Config {
 struct RawLexer<'a> {
   #[serde(borrow)]
   priority: Vec<RawPriorityRow<'a>>,
+  #[serde(default, borrow)] resolve: Vec<RawResolve<'a>>,
+  #[serde(default, borrow)] pratt: Vec<&'a str>,
   lexical: IndexMap<std::borrow::Cow<'a, str>, &'a str>,
   #[serde(default)] lexer_field: &'a str,
   #[serde(default)] lexer_action: &'a str,
@@ -63,22 +73,27 @@ fn work(attr: TokenStream, input: TokenStream, algo: PGAlgo) -> TokenStream {
   let start = &attr.to_string();
   let parser_def = parser.self_ty.to_token_stream().to_string();
 
-  let Config { lex, lex_path, verbose, show_fsm, show_dfa, log_token, log_reduce, use_unsafe, expand }
+  let Config { lex, lex_path, verbose, show_fsm, show_dfa, log_token, log_reduce, use_unsafe, glr, user_parse, ll_k, messages, repair, stream, incremental, cst, expand }
     = Config::from_list(&parse_attrs(&parser.attrs)).expect("failed to read attributes");
   let lex = if let Some(lex) = lex { lex } else {
     std::fs::read_to_string(lex_path.expect("attributes must contain `lex` or `lex_path`")).expect("failed to read lex")
   };
-  let mut cfg = parser_gen::Config {
-    verbose: verbose.as_deref(),
-    show_fsm: show_fsm.as_deref(),
-    show_dfa: show_dfa.as_deref(),
-    log_token,
-    log_reduce,
-    use_unsafe,
-    lang: Lang::Rs,
-    on_conflict: |c| Diagnostic::new(Level::Warning, c).emit(),
-    code_output: Vec::new(),
-  };
+  let mut cfg = parser_gen::Config::new(Lang::Rs, Vec::new());
+  cfg.verbose = verbose.as_deref();
+  cfg.show_fsm = show_fsm.as_deref();
+  cfg.show_dfa = show_dfa.as_deref();
+  cfg.log_token = log_token;
+  cfg.log_reduce = log_reduce;
+  cfg.use_unsafe = use_unsafe;
+  cfg.glr = glr;
+  cfg.user_parse = user_parse;
+  cfg.ll_k = ll_k;
+  cfg.messages = messages.as_deref();
+  cfg.repair = repair;
+  cfg.stream = stream;
+  cfg.incremental = incremental;
+  cfg.cst = cst;
+  cfg.on_conflict = |c| Diagnostic::new(Level::Warning, c).emit();
   let lex = toml::from_str::<RawLexer>(&lex).expect("failed to parse lexer toml");
 
   let mut production = Vec::new();
@@ -90,6 +105,11 @@ fn work(attr: TokenStream, input: TokenStream, algo: PGAlgo) -> TokenStream {
       let prec = prec.map(|x| &*arena.alloc_str(&x));
       let (lhs, rhs) = parse_arrow_prod(&rule).unwrap_or_else(||
         panic!("rule \"{}\" of method `{}` is not in the form of \"lhs -> rhs1 rhs2 ...\"", rule, method.sig.ident));
+      // in cst mode, the signature and body of the method are ignored
+      if cst {
+        production.push(RawProduction { lhs, ty: "", rhs: vec![RawProductionRhs { rhs, rhs_arg: None, act: "", prec }] });
+        continue;
+      }
       let ty = match &method.sig.output {
         ReturnType::Default => "()",
         ReturnType::Type(_, ty) => arena.alloc_str(&ty.to_token_stream().to_string()),
@@ -108,6 +128,8 @@ fn work(attr: TokenStream, input: TokenStream, algo: PGAlgo) -> TokenStream {
   let g = RawGrammar {
     include: "",
     priority: lex.priority,
+    resolve: lex.resolve,
+    pratt: lex.pratt,
     lexical: lex.lexical,
     lexer_field: lex.lexer_field,
     lexer_action: lex.lexer_action,
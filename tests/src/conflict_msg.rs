@@ -0,0 +1,98 @@
+use common::*;
+use lalr1_core::*;
+use parser_gen::show_lr;
+
+const GRAMMAR: &str = "E -> E Add E
+E -> E Mul E
+E -> Id
+";
+
+// left associative `priority` rows, each row is a space separated list of terms
+fn left(rows: &[&'static str]) -> Vec<RawPriorityRow<'static>> {
+  rows.iter().map(|r| RawPriorityRow { assoc: Assoc::Left, terms: r.split_whitespace().collect() }).collect()
+}
+
+// the unresolved conflicts and the warnings about useless priorities of the grammar, after `edit` on the raw grammar
+fn report(src: &'static str, edit: impl FnOnce(&mut RawGrammar<'static>)) -> (Vec<String>, Vec<String>) {
+  let mut raw = parse_lines(src).unwrap();
+  edit(&mut raw);
+  let ref g = raw.extend(false).unwrap();
+  let lalr1 = lalr1_by_lr0::work(lr0::work(g), g);
+  let mut table = mk_table::mk_table(&lalr1, g);
+  let (conflict, used) = mk_table::solve(&mut table, g);
+  (show_lr::conflict(g, &conflict), show_lr::unused_priority(g, &used))
+}
+
+#[test]
+fn suggest_priority() {
+  let (c, w) = report("E -> E Add E\nE -> Id\n", |_| {});
+  assert_eq!(c.len(), 1);
+  assert!(c[0].ends_with("\nhelp: add row `{ assoc = 'right', terms = ['Add'] }` to `priority` to keep shifting, \
+    or row `{ assoc = 'left', terms = ['Add'] }` to reduce instead (usual for a left associative binary operator)"));
+  assert!(w.is_empty());
+
+  // reducing `E -> E Add E` or shifting `Mul`, and so on
+  let (c, _) = report(GRAMMAR, |_| {});
+  assert_eq!(c.len(), 4);
+  assert!(c.iter().any(|c| c.contains("\nhelp: add rows `{ assoc = 'left', terms = ['Add'] }, { assoc = 'left', terms = ['Mul'] }` to `priority`")));
+
+  // the conflicts between `Add` and `Add` are solved, one side of the conflicts between `Add` and `Mul` has a priority
+  let (c, _) = report(GRAMMAR, |g| g.priority = left(&["Add"]));
+  assert_eq!(c.len(), 3);
+  assert!(c.iter().any(|c| c.contains("\nhelp: \"Mul\" has no priority, add it to a row after the row 0 (0-based) of \"Add\" in `priority` to keep shifting")));
+  assert!(c.iter().any(|c| c.contains("\nhelp: \"Mul\" has no priority, add it to a row before the row 0 (0-based) of \"Add\" in `priority` to keep shifting")));
+  assert!(c.iter().any(|c| c.contains("\nhelp: add row `{ assoc = 'right', terms = ['Mul'] }`")));
+
+  // all solved
+  let (c, w) = report(GRAMMAR, |g| g.priority = left(&["Add", "Mul"]));
+  assert!(c.is_empty() && w.is_empty());
+}
+
+#[test]
+fn unused_priority() {
+  const PAREN: &str = "E -> E Add E
+E -> E Mul E
+E -> Id
+E -> LPar E RPar
+";
+  let (_, w) = report(PAREN, |g| g.priority = left(&["Add Id", "Mul", "LPar RPar"]));
+  assert_eq!(w, vec![
+    "priority of term \"Id\" (row 0) never decides a conflict".to_owned(),
+    "priority row 2 (\"LPar\", \"RPar\") never decides a conflict".to_owned(),
+  ]);
+
+  let (_, w) = report(PAREN, |g| {
+    g.priority = left(&["Add", "Mul"]);
+    g.production[1].rhs[0].prec = Some("Id");
+    g.production[3].rhs[0].prec = Some("Add");
+  });
+  assert_eq!(w, vec![
+    "prec \"Id\" of production \"E -> E Mul E\" is ineffective, because \"Id\" has no priority".to_owned(),
+    "prec \"Add\" of production \"E -> LPar E RPar\" never decides a conflict".to_owned(),
+  ]);
+}
+
+#[test]
+fn many() {
+  // the same as `multi_conflict.rs`, shifting `Id`, reducing `A -> Id` and reducing `B -> Id` are solved by priority
+  const MANY: &str = "S -> A Id
+S -> B Id
+S -> Id Id Id
+S -> Lo Hi
+A -> Id
+B -> Id
+";
+  let (c, _) = report(MANY, |g| {
+    g.priority = left(&["Lo", "Id", "Hi"]);
+    g.production[4].rhs[0].prec = Some("Hi");
+    g.production[5].rhs[0].prec = Some("Lo");
+  });
+  assert_eq!(c.len(), 1);
+  assert!(c[0].starts_with("3-way conflict at state "));
+  assert!(c[0].contains("when faced with token \"Id\", it is solved by priority, the first one is chosen:\n  - reduce 4(\"A -> Id\")\n  - shift "));
+
+  // accepting and reducing `A -> S` on EOF can't be decided
+  let (c, _) = report("S -> A\nA -> S\nA -> x\n", |_| {});
+  assert_eq!(c.len(), 1);
+  assert!(c[0].ends_with("the first one is chosen:\n  - accept\n  - reduce 1(\"A -> S\")\n"));
+}
@@ -0,0 +1,130 @@
+use parser_gen::{Config, Lang, PGAlgo};
+use std::{fs, path::{Path, PathBuf}, process::Command};
+
+// generate the parser of `parser-gen/examples/{toml}` in `lang` as `dir/file`, `dir` is a new directory named after `toml`
+fn gen(toml: &str, algo: PGAlgo, lang: Lang, stream: bool, file: &str) -> PathBuf {
+  let src = fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("../parser-gen/examples").join(toml)).unwrap();
+  let raw = toml::from_str(&src).unwrap();
+  let mut cfg = Config::new(lang, Vec::new());
+  cfg.stream = stream;
+  cfg.on_conflict = |c| panic!("{}", c);
+  parser_gen::work(raw, algo, &mut cfg).unwrap();
+  let dir = std::env::temp_dir().join(format!("lalr1_tests_{}", toml.trim_end_matches(".toml")));
+  fs::create_dir_all(&dir).unwrap();
+  fs::write(dir.join(file), cfg.code_output).unwrap();
+  dir
+}
+
+// run `cmd` in `dir` and return its stdout, or None if `cmd` is not installed
+fn run(dir: &Path, cmd: &str, args: &[&str]) -> Option<String> {
+  let out = Command::new(cmd).args(args).current_dir(dir).output().ok()?;
+  assert!(out.status.success(), "{} {:?} failed: {}", cmd, args, String::from_utf8_lossy(&out.stderr));
+  Some(String::from_utf8(out.stdout).unwrap())
+}
+
+// prints the value of the expression in argv[1], or the position of the token where the error is detected
+const CALC_CPP: &str = r#"#include "calc.cpp"
+#include <cstdio>
+
+int main(int, char **argv) {
+  Lexer lexer(argv[1]);
+  auto r = Parser{}.parse(lexer);
+  if (auto v = std::get_if<i32>(&r)) { printf("%d", *v); }
+  else { Token &t = std::get<Token>(r); printf("error %u:%u", t.line, t.col); }
+}
+"#;
+
+const CALC_JAVA: &str = r#"public class Main {
+  public static void main(String[] args) {
+    Parser.Result r = new Parser().parse(new Parser.Lexer(args[0].getBytes()));
+    System.out.print(r.err == null ? String.valueOf(r.ok) : "error " + r.err.line + ":" + r.err.col);
+  }
+}
+"#;
+
+// the input of the stream tests, it is longer than a chunk of `StreamLexer`, so tokens are split by chunk boundaries
+// lexes it by both `Lexer` and `StreamLexer`, and prints the number of tokens if they are the same, and the sum of the parse result
+const STREAM_CPP: &str = r#"#include "calc.cpp"
+#include <cstdio>
+#include <sstream>
+
+int main() {
+  std::string s;
+  for (int i = 0; i < 20000; ++i) { s += "12 + 3;\n"; }
+  s += "1 + ;";
+  Lexer lexer(s);
+  std::istringstream in(s);
+  StreamLexer stream(in);
+  u32 count = 0;
+  while (true) {
+    Token t1 = lexer.next(), t2 = stream.next();
+    if (t1.kind != t2.kind || t1.piece != t2.piece || t1.line != t2.line || t1.col != t2.col) { return printf("mismatch %u:%u", t1.line, t1.col), 0; }
+    if (t1.kind == Token::_Eof) { break; }
+    ++count;
+  }
+  Lexer lexer1(s);
+  Parser parser;
+  auto r = parser.parse(lexer1);
+  i32 sum = 0;
+  for (i32 x : std::get<std::vector<i32>>(r)) { sum += x; }
+  printf("%u %d %zu", count, sum, parser.errors.size());
+}
+"#;
+
+const STREAM_JAVA: &str = r#"public class Main {
+  public static void main(String[] args) throws java.io.IOException {
+    StringBuilder sb = new StringBuilder();
+    for (int i = 0; i < 20000; ++i) { sb.append("12 + 3;\n"); }
+    byte[] s = sb.append("1 + ;").toString().getBytes();
+    Parser.Lexer lexer = new Parser.Lexer(s);
+    Parser.StreamLexer stream = new Parser.StreamLexer(new java.io.ByteArrayInputStream(s));
+    int count = 0;
+    while (true) {
+      Parser.Token t1 = lexer.next(), t2 = stream.next();
+      if (t1.kind != t2.kind || !t1.piece.equals(t2.piece) || t1.line != t2.line || t1.col != t2.col) {
+        System.out.print("mismatch " + t1.line + ":" + t1.col);
+        return;
+      }
+      if (t1.kind == 1) { break; }
+      ++count;
+    }
+    Parser parser = new Parser();
+    int sum = parser.parse(new Parser.Lexer(s)).ok.stream().mapToInt(x -> x).sum();
+    System.out.print(count + " " + sum + " " + parser.errors.size());
+  }
+}
+"#;
+
+#[test]
+fn ll1_cpp() {
+  let dir = gen("calc_ll1_cpp.toml", PGAlgo::LL1, Lang::Cpp, false, "calc.cpp");
+  fs::write(dir.join("main.cpp"), CALC_CPP).unwrap();
+  if run(&dir, "g++", &["-std=c++17", "main.cpp", "-o", "calc"]).is_none() { return eprintln!("g++ is not found, skip the test"); }
+  assert_eq!(run(&dir, dir.join("calc").to_str().unwrap(), &["1 + 2 * 3 - -4 % (5 - 2)"]).unwrap(), "8");
+  assert_eq!(run(&dir, dir.join("calc").to_str().unwrap(), &["1 + * 2"]).unwrap(), "error 1:5");
+}
+
+#[test]
+fn ll1_java() {
+  let dir = gen("calc_ll1_java.toml", PGAlgo::LL1, Lang::Java, false, "Parser.java");
+  fs::write(dir.join("Main.java"), CALC_JAVA).unwrap();
+  if run(&dir, "javac", &["-d", ".", "Parser.java", "Main.java"]).is_none() { return eprintln!("javac is not found, skip the test"); }
+  assert_eq!(run(&dir, "java", &["Main", "1 + 2 * 3 - -4 % (5 - 2)"]).unwrap(), "8");
+  assert_eq!(run(&dir, "java", &["Main", "1 + * 2"]).unwrap(), "error 1:5");
+}
+
+#[test]
+fn stream_cpp() {
+  let dir = gen("calc_error_cpp.toml", PGAlgo::LALR1, Lang::Cpp, true, "calc.cpp");
+  fs::write(dir.join("main.cpp"), STREAM_CPP).unwrap();
+  if run(&dir, "g++", &["-std=c++17", "main.cpp", "-o", "calc"]).is_none() { return eprintln!("g++ is not found, skip the test"); }
+  assert_eq!(run(&dir, dir.join("calc").to_str().unwrap(), &[]).unwrap(), "80003 300000 1");
+}
+
+#[test]
+fn stream_java() {
+  let dir = gen("calc_error_java.toml", PGAlgo::LALR1, Lang::Java, true, "Parser.java");
+  fs::write(dir.join("Main.java"), STREAM_JAVA).unwrap();
+  if run(&dir, "javac", &["-d", ".", "Parser.java", "Main.java"]).is_none() { return eprintln!("javac is not found, skip the test"); }
+  assert_eq!(run(&dir, "java", &["Main"]).unwrap(), "80003 300000 1");
+}
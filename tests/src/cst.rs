@@ -0,0 +1,61 @@
+use parser_macros::lalr1;
+
+struct Parser;
+
+#[lalr1(Expr)]
+#[cst]
+#[lex = r#"
+priority = [{ assoc = 'left', terms = ['Add'] }]
+
+[lexical]
+'\(' = 'LPar'
+'\)' = 'RPar'
+'\+' = 'Add'
+'\d+' = 'IntLit'
+'\s+' = '_Eps'
+"#]
+impl Parser {
+  #[rule = "Expr -> Expr Add Expr"]
+  fn expr_add() {}
+  #[rule = "Expr -> LPar Expr RPar"]
+  fn expr_paren() {}
+  #[rule = "Expr -> IntLit"]
+  fn expr_int() {}
+}
+
+#[test]
+fn cst() {
+  let node = Parser.parse(&mut Lexer::new(b"1 + (2)")).unwrap();
+  assert_eq!((node.kind, node.prod, node.children.len()), (NonTermKind::Expr, 0, 3));
+  match &node.children[1] {
+    Child::Token(t) => assert_eq!((t.kind, t.piece), (TokenKind::Add, &b"+"[..])),
+    _ => panic!("expect a token"),
+  }
+  assert_eq!(node.to_string(), r#"Expr -> Expr Add Expr
+  Expr -> IntLit
+    IntLit "1"
+  Add "+"
+  Expr -> LPar Expr RPar
+    LPar "("
+    Expr -> IntLit
+      IntLit "2"
+    RPar ")"
+"#);
+}
+
+// generate a rust lalr1 parser for a grammar without types and actions
+fn gen(cst: bool) {
+  use parser_gen::{Config, Lang, PGAlgo};
+  let raw = common::parse_lines("S -> a S\nS ->\n").unwrap();
+  let mut cfg = Config::new(Lang::Rs, Vec::new());
+  cfg.cst = cst;
+  cfg.on_conflict = |_| {};
+  parser_gen::work(raw, PGAlgo::LALR1, &mut cfg).unwrap();
+}
+
+#[test]
+fn omit_ty_act() { gen(true); }
+
+#[test]
+#[should_panic(expected = "non-term \"S\" has no type, it can only be omitted in cst mode")]
+fn require_ty_act() { gen(false); }
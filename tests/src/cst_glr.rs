@@ -0,0 +1,55 @@
+use parser_macros::lalr1;
+
+// `merged` counts the calls of `_merge`
+struct Parser { merged: u32 }
+
+#[lalr1(Expr)]
+#[glr]
+#[cst]
+#[lex = r#"
+priority = []
+
+[lexical]
+'\+' = 'Add'
+'\*' = 'Mul'
+'\d+' = 'IntLit'
+'\s+' = '_Eps'
+"#]
+impl Parser {
+  #[rule = "Expr -> Expr Add Expr"]
+  fn expr_add() {}
+  #[rule = "Expr -> Expr Mul Expr"]
+  fn expr_mul() {}
+  #[rule = "Expr -> IntLit"]
+  fn expr_int() {}
+}
+
+impl Parser {
+  // all non-terminals have type `Node` in cst mode, prefer the tree whose root is `Expr -> Expr Add Expr`
+  fn _merge<'p>(&mut self, _nt: u32, l: StackItem<'p>, r: StackItem<'p>) -> StackItem<'p> {
+    self.merged += 1;
+    match (l, r) {
+      (StackItem::_0(l), StackItem::_0(r)) => StackItem::_0(if l.prod == 0 { l } else { r }),
+      _ => unreachable!(),
+    }
+  }
+}
+
+#[test]
+fn cst_glr() {
+  let mut parser = Parser { merged: 0 };
+  let node = parser.parse(&mut Lexer::new(b"1 + 2 * 3")).unwrap();
+  assert_eq!(parser.merged, 1);
+  assert_eq!(node.to_string(), r#"Expr -> Expr Add Expr
+  Expr -> IntLit
+    IntLit "1"
+  Add "+"
+  Expr -> Expr Mul Expr
+    Expr -> IntLit
+      IntLit "2"
+    Mul "*"
+    Expr -> IntLit
+      IntLit "3"
+"#);
+  assert_eq!(Parser { merged: 0 }.parse(&mut Lexer::new(b"1 + * 2")).map_err(|e| e.token.kind), Err(TokenKind::Mul));
+}
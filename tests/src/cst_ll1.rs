@@ -0,0 +1,42 @@
+use parser_macros::ll1;
+use common::{HashSet, HashMap};
+
+struct Parser;
+
+#[ll1(Expr)]
+#[cst]
+#[lex = r#"
+priority = []
+
+[lexical]
+'\+' = 'Add'
+'\d+' = 'IntLit'
+'\s+' = '_Eps'
+"#]
+impl Parser {
+  #[rule = "Expr -> IntLit Expr1"]
+  fn expr() {}
+  #[rule = "Expr1 -> Add IntLit Expr1"]
+  fn expr1() {}
+  #[rule = "Expr1 ->"]
+  fn expr1_empty() {}
+}
+
+#[test]
+fn cst_ll1() {
+  let node = Parser.parse(&mut Lexer::new(b"1 + 2")).unwrap();
+  assert_eq!((node.kind, node.prod, node.children.len()), (NonTermKind::Expr, 0, 2));
+  match &node.children[1] {
+    Child::Node(x) => assert_eq!((x.kind, x.prod), (NonTermKind::Expr1, 1)),
+    _ => panic!("expect a node"),
+  }
+  // the empty production builds a node without children
+  assert_eq!(node.to_string(), r#"Expr -> IntLit Expr1
+  IntLit "1"
+  Expr1 -> Add IntLit Expr1
+    Add "+"
+    IntLit "2"
+    Expr1 ->
+"#);
+  assert!(Parser.parse(&mut Lexer::new(b"1 + + 2")).is_err());
+}
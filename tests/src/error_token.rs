@@ -0,0 +1,48 @@
+use parser_macros::lalr1;
+
+// the columns of the `error` tokens
+struct Parser(Vec<u32>);
+
+// a list of statements, a broken statement is skipped until the next `;`
+#[lalr1(Stmts)]
+#[lex = r#"
+priority = []
+
+[lexical]
+';' = 'Semi'
+'\+' = 'Add'
+'\d+' = 'IntLit'
+'\s+' = '_Eps'
+'//[^\n]*' = '_Eps'
+"#]
+impl Parser {
+  #[rule = "Stmts -> Stmts Stmt"]
+  fn stmts(mut l: Vec<i32>, r: i32) -> Vec<i32> { (l.push(r), l).1 }
+  #[rule = "Stmts ->"]
+  fn stmts0() -> Vec<i32> { vec![] }
+  #[rule = "Stmt -> Expr Semi"]
+  fn stmt(e: i32, _: Token) -> i32 { e }
+  #[rule = "Stmt -> error Semi"]
+  fn stmt_err(&mut self, e: Token, _: Token) -> i32 { (self.0.push(e.col), -1).1 }
+  #[rule = "Expr -> Expr Add IntLit"]
+  fn expr_add(l: i32, _: Token, r: Token) -> i32 { l + std::str::from_utf8(r.piece).unwrap().parse::<i32>().unwrap() }
+  #[rule = "Expr -> IntLit"]
+  fn expr_int(i: Token) -> i32 { std::str::from_utf8(i.piece).unwrap().parse().unwrap() }
+}
+
+#[test]
+fn error_token() {
+  let mut p = Parser(vec![]);
+  let (res, errors) = p.parse(&mut Lexer::new(b"1 + 2; 3 + + 4; 5 6; 7;")).unwrap();
+  assert_eq!(res, vec![3, -1, -1, 7]);
+  assert_eq!(p.0, [12, 19]);
+  // each recovery reports the error that causes it
+  assert_eq!(errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+    [r#"1:12: unexpected Add "+", expected IntLit"#, r#"1:19: unexpected IntLit "6", expected Semi or Add"#]);
+  // an invalid token (a single `/` is not a comment) is not shifted as `error`, but it is recovered in the same way
+  let (res, errors) = p.parse(&mut Lexer::new(b"1 + 2; 3 / 4; 5;")).unwrap();
+  assert_eq!(res, vec![3, -1, 5]);
+  assert_eq!(errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(), [r#"1:10: invalid token "/""#]);
+  // no statement can end before `;`, so the last error is unrecoverable
+  assert_eq!(p.parse(&mut Lexer::new(b"1 + ; 2")).map_err(|e| e.token.kind), Err(TokenKind::_Eof));
+}
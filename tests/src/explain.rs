@@ -0,0 +1,37 @@
+use common::*;
+use lalr1_core::*;
+use ll1_core::*;
+
+const GRAMMAR: &str = "S -> A x
+A -> B C
+B -> b
+C -> c
+C ->
+";
+
+#[test]
+fn explain() {
+  let mut raw = parse_lines(GRAMMAR).unwrap();
+  let ref g = raw.extend(false).unwrap();
+  let x = g.terms.iter().position(|t| t.name == "x").unwrap() as u32;
+  let lalr1 = lalr1_by_lr0::work(lr0::work(g), g);
+  // find the item "B -> b." and explain `x` in its lookahead
+  let (state, item) = lalr1.iter().enumerate().find_map(|(s, n)| n.closure.iter()
+    .position(|it| g.nt[g.prod[it.prod_id as usize].lhs as usize].name == "B" && it.dot == 1).map(|i| (s as u32, i as u32))).unwrap();
+  let chain = lalr1_by_lr0::explain(&lalr1, g, state, item, x).unwrap();
+  // x is generated by the closure of "S -> .A x", propagated to "B -> .b" (C is nullable), then to "B -> b." by goto
+  assert_eq!(chain.iter().map(|x| x.2).collect::<Vec<_>>(),
+    vec![LookaheadReason::Spontaneous { from: 0 }, LookaheadReason::Closure, LookaheadReason::Goto]);
+  assert_eq!(chain.last().map(|x| (x.0, x.1)), Some((state, item)));
+  assert!(lalr1_by_lr0::explain(&lalr1, g, state, item, EOF_IDX as u32).is_none());
+
+  let ll = LLCtx::new(g);
+  let b = g.nt.iter().position(|t| t.name == "B").unwrap();
+  let chain = explain_follow(g, &ll.first, &ll.follow, b, x).unwrap();
+  assert_eq!(chain.len(), 2);
+  assert!(matches!(chain[0].1, FollowReason::First { .. }));
+  assert!(matches!(chain[1].1, FollowReason::Lhs { .. }));
+  let s = g.nt.iter().position(|t| t.name == "S").unwrap();
+  let b_tk = g.terms.iter().position(|t| t.name == "b").unwrap() as u32;
+  assert_eq!(explain_first(g, &ll.first, s, b_tk).map(|x| x.len()), Some(3));
+}
@@ -0,0 +1,45 @@
+use parser_macros::lalr1;
+
+struct Parser;
+
+// the value of an expression is the list of the results of all its possible parse trees
+#[lalr1(Expr)]
+#[glr]
+#[lex = r#"
+priority = []
+
+[lexical]
+'\+' = 'Add'
+'\*' = 'Mul'
+'\d+' = 'IntLit'
+'\s+' = '_Eps'
+"#]
+impl Parser {
+  #[rule = "Expr -> Expr Add Expr"]
+  fn expr_add(l: Vec<i32>, _op: Token, r: Vec<i32>) -> Vec<i32> { l.iter().flat_map(|l| r.iter().map(move |r| l + r)).collect() }
+  #[rule = "Expr -> Expr Mul Expr"]
+  fn expr_mul(l: Vec<i32>, _op: Token, r: Vec<i32>) -> Vec<i32> { l.iter().flat_map(|l| r.iter().map(move |r| l * r)).collect() }
+  #[rule = "Expr -> IntLit"]
+  fn expr_int(i: Token) -> Vec<i32> { vec![std::str::from_utf8(i.piece).unwrap().parse().unwrap()] }
+}
+
+impl Parser {
+  fn _merge<'p>(&mut self, _nt: u32, l: StackItem<'p>, r: StackItem<'p>) -> StackItem<'p> {
+    match (l, r) {
+      (StackItem::_0(mut l), StackItem::_0(r)) => (l.extend(r), StackItem::_0(l)).1,
+      _ => unreachable!(),
+    }
+  }
+}
+
+#[test]
+fn glr() {
+  let mut res = Parser.parse(&mut Lexer::new(b"1 + 2 * 3")).unwrap();
+  res.sort();
+  assert_eq!(res, vec![7, 9]);
+  // catalan number, there are 5 ways to parenthesize 4 operands
+  assert_eq!(Parser.parse(&mut Lexer::new(b"1 + 1 + 1 + 1")).unwrap(), vec![4; 5]);
+  let e = Parser.parse(&mut Lexer::new(b"1 + * 2")).unwrap_err();
+  assert_eq!((e.token.kind, e.expected), (TokenKind::Mul, vec![TokenKind::IntLit]));
+  assert_eq!(e.to_string(), r#"1:5: unexpected Mul "*", expected IntLit"#);
+}
@@ -0,0 +1,84 @@
+use parser_macros::lalr1;
+use std::rc::Rc;
+
+struct Parser;
+
+#[lalr1(Prog)]
+#[incremental]
+#[lex = r#"
+priority = [{ assoc = 'left', terms = ['Add'] }]
+
+[lexical]
+'=' = 'Assign'
+';' = 'Semi'
+'\+' = 'Add'
+'\d+' = 'IntLit'
+'[a-z]+' = 'Id'
+'\s+' = '_Eps'
+"#]
+impl Parser {
+  #[rule = "Prog ->"]
+  fn prog0() {}
+  #[rule = "Prog -> Prog Stmt"]
+  fn prog(_p: (), _s: ()) {}
+  #[rule = "Stmt -> Id Assign Expr Semi"]
+  fn stmt(_l: Token, _a: Token, _r: (), _s: Token) {}
+  #[rule = "Expr -> Expr Add Expr"]
+  fn expr_add(_l: (), _op: Token, _r: ()) {}
+  #[rule = "Expr -> IntLit"]
+  fn expr_int(_i: Token) {}
+  #[rule = "Expr -> Id"]
+  fn expr_id(_i: Token) {}
+}
+
+// the `Stmt` nodes in a `Prog` node
+fn stmts(p: &Rc<IncNode>) -> Vec<Rc<IncNode>> {
+  match &**p {
+    IncNode::Node { children, .. } if children.len() == 2 => {
+      let mut v = stmts(&children[0]);
+      v.push(children[1].clone());
+      v
+    }
+    _ => vec![],
+  }
+}
+
+fn full_parse(text: &[u8]) -> Incremental {
+  let mut inc = Incremental::new(text.to_vec());
+  assert!(inc.parse().is_ok());
+  inc
+}
+
+#[test]
+fn incremental() {
+  let mut inc = full_parse(b"a = 1;\nb = a + 2;\nc = b + 3;\n");
+  let old = stmts(inc.tree().unwrap());
+  assert_eq!(old.len(), 3);
+  // "2" -> "20"
+  assert!(inc.edit(16..16, b"0").is_ok());
+  assert_eq!(inc.tree(), full_parse(inc.text()).tree());
+  let mid = stmts(inc.tree().unwrap());
+  assert!(Rc::ptr_eq(&old[0], &mid[0]) && !Rc::ptr_eq(&old[1], &mid[1]) && Rc::ptr_eq(&old[2], &mid[2]));
+  // "c = b + 3;" -> "c = b + ;"
+  let e = inc.edit(27..28, b"").unwrap_err();
+  assert_eq!((e.token.kind, e.token.line, e.token.col), (TokenKind::Semi, 3, 9));
+  // the tree of the last successful parse is still used
+  assert!(inc.edit(27..27, b"4").is_ok());
+  assert_eq!(inc.text(), b"a = 1;\nb = a + 20;\nc = b + 4;\n");
+  assert_eq!(inc.tree(), full_parse(inc.text()).tree());
+  let new = stmts(inc.tree().unwrap());
+  assert!(Rc::ptr_eq(&mid[0], &new[0]) && Rc::ptr_eq(&mid[1], &new[1]) && !Rc::ptr_eq(&mid[2], &new[2]));
+}
+
+#[test]
+#[should_panic(expected = "the reserved terminal `error` is not supported by incremental parsing")]
+fn reject_error() {
+  use parser_gen::{Config, Lang, PGAlgo};
+  let mut raw = common::parse_lines("S -> a S\nS -> error\n").unwrap();
+  raw.lexical.retain(|_, &mut t| t != common::ERROR);
+  let mut cfg = Config::new(Lang::Rs, Vec::new());
+  cfg.incremental = true;
+  cfg.cst = true;
+  cfg.on_conflict = |_| {};
+  parser_gen::work(raw, PGAlgo::LALR1, &mut cfg).unwrap();
+}
@@ -33,4 +33,7 @@ impl Parser {
 #[test]
 fn lalr1() {
   assert_eq!(Parser.parse(&mut Lexer::new(b"1 - 2 * (3 + 4 * 5 / 6) + -7 * -9 % 10")), Ok(-8));
+  let e = Parser.parse(&mut Lexer::new(b"1 + )")).unwrap_err();
+  assert_eq!((e.token.kind, &e.expected[..]), (TokenKind::RPar, &[TokenKind::Sub, TokenKind::LPar, TokenKind::IntLit][..]));
+  assert_eq!(e.to_string(), r#"1:5: unexpected RPar ")", expected Sub, LPar or IntLit"#);
 }
@@ -0,0 +1,40 @@
+use common::*;
+use lalr1_core::*;
+
+// the classic grammar that is lr1 but not lalr1, state {A -> c., B -> c.} after `a` and after `b` are merged
+const GRAMMAR: &str = "S -> a A d
+S -> b B d
+S -> a B e
+S -> b A e
+A -> c
+B -> c
+";
+
+#[test]
+fn lalr_only() {
+  let mut raw = parse_lines(GRAMMAR).unwrap();
+  let ref g = raw.extend(false).unwrap();
+  let lalr1 = lalr1_by_lr0::work(lr0::work(g), g);
+  let mut table = mk_table::mk_table(&lalr1, g);
+  let mut conflict = mk_table::solve(&mut table, g).0;
+  assert_eq!(conflict.len(), 2); // on `d` and `e`
+  let lr1 = lr1::work(g);
+  mk_table::mark_lalr_only(&mut conflict, &lalr1, &lr1);
+  for c in &conflict {
+    let merged = c.lalr_only.as_ref().unwrap();
+    // one state reduces `A -> c`, the other reduces `B -> c`
+    assert_eq!(merged.len(), 2);
+    assert_ne!(merged[0].0, merged[1].0);
+    assert_ne!(merged[0].1, merged[1].1);
+  }
+
+  // a real ambiguity also exists in canonical lr1
+  let mut raw = parse_lines("S -> A\nS -> B\nA -> c\nB -> c\n").unwrap();
+  let ref g = raw.extend(false).unwrap();
+  let lalr1 = lalr1_by_lr0::work(lr0::work(g), g);
+  let mut table = mk_table::mk_table(&lalr1, g);
+  let mut conflict = mk_table::solve(&mut table, g).0;
+  mk_table::mark_lalr_only(&mut conflict, &lalr1, &lr1::work(g));
+  assert_eq!(conflict.len(), 1);
+  assert!(conflict[0].lalr_only.is_none());
+}
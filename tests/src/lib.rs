@@ -1,7 +1,47 @@
 #![feature(proc_macro_hygiene)]
 #[cfg(test)]
+mod conflict_msg;
+#[cfg(test)]
+mod cpp_java;
+#[cfg(test)]
+mod cst;
+#[cfg(test)]
+mod cst_glr;
+#[cfg(test)]
+mod cst_ll1;
+#[cfg(test)]
+mod error_token;
+#[cfg(test)]
+mod explain;
+#[cfg(test)]
+mod glr;
+#[cfg(test)]
+mod incremental;
+#[cfg(test)]
 mod lalr1;
 #[cfg(test)]
+mod lalr_only;
+#[cfg(test)]
 mod ll1;
 #[cfg(test)]
-mod literal_string;
\ No newline at end of file
+mod ll1_parse;
+#[cfg(test)]
+mod ll_k;
+#[cfg(test)]
+mod literal_string;
+#[cfg(test)]
+mod messages;
+#[cfg(test)]
+mod multi_conflict;
+#[cfg(test)]
+mod pratt;
+#[cfg(test)]
+mod push;
+#[cfg(test)]
+mod repair;
+#[cfg(test)]
+mod resolve;
+#[cfg(test)]
+mod sets;
+#[cfg(test)]
+mod stream;
\ No newline at end of file
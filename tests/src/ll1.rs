@@ -7,6 +7,7 @@ pub enum Op { Add, Sub, Mul, Div, Mod }
 
 #[ll1(Expr)]
 #[lex_path = "tests/src/lex.toml"]
+#[user_parse]
 impl Parser {
   #[rule = "Expr -> Term1 Expr1"]
   fn r0(mut t: i32, remain: Vec<(Op, i32)>) -> i32 {
@@ -0,0 +1,46 @@
+use parser_macros::ll1;
+use common::{HashSet, HashMap};
+
+struct Parser;
+
+// `_parse` is generated, the value is the sum of all integers
+#[ll1(Expr)]
+#[lex = r#"
+priority = []
+
+[lexical]
+'\(' = 'LPar'
+'\)' = 'RPar'
+'\+' = 'Add'
+'\d+' = 'IntLit'
+'\s+' = '_Eps'
+"#]
+impl Parser {
+  #[rule = "Expr -> Term Expr1"]
+  fn expr(l: i32, r: i32) -> i32 { l + r }
+
+  #[rule = "Expr1 -> Add Term Expr1"]
+  fn expr1(_: Token, l: i32, r: i32) -> i32 { l + r }
+
+  #[rule = "Expr1 ->"]
+  fn expr1_empty() -> i32 { 0 }
+
+  #[rule = "Term -> IntLit"]
+  fn term(i: Token) -> i32 { std::str::from_utf8(i.piece).unwrap().parse().unwrap() }
+
+  #[rule = "Term -> LPar Expr RPar"]
+  fn term_paren(_: Token, x: i32, _: Token) -> i32 { x }
+}
+
+#[test]
+fn ll1_parse() {
+  assert_eq!(Parser.parse(&mut Lexer::new(b"1 + (2 + 3) + ((4))")), Ok(10));
+  // `_parse` is not recursive, so deeply nested input won't overflow the stack
+  let deep = format!("{}1{}", "(".repeat(100000), ")".repeat(100000));
+  assert_eq!(Parser.parse(&mut Lexer::new(deep.as_bytes())), Ok(1));
+  // errors are reported at the tokens where they are detected, and parsing continues after skipping some tokens
+  let errors = |s| Parser.parse(&mut Lexer::new(s)).unwrap_err().iter().map(|t| (t.kind, t.col)).collect::<Vec<_>>();
+  assert_eq!(errors(b"1 + (2 + 3"), [(TokenKind::_Eof, 11)]);
+  assert_eq!(errors(b"1 + 2 )"), [(TokenKind::RPar, 7)]);
+  assert_eq!(errors(b"1 + + 2 + (3 4) + )"), [(TokenKind::Add, 5), (TokenKind::IntLit, 14), (TokenKind::RPar, 19)]);
+}
@@ -0,0 +1,40 @@
+use parser_macros::ll1;
+use common::{HashSet, HashMap};
+
+struct Parser;
+
+// "Id Colon" and "Id Assign" can only be distinguished by 2 tokens
+#[ll1(Stmts)]
+#[ll_k = 2]
+#[lex = r#"
+priority = []
+
+[lexical]
+':' = 'Colon'
+'=' = 'Assign'
+';' = 'Semi'
+'\d+' = 'IntLit'
+'[a-z]+' = 'Id'
+'\s+' = '_Eps'
+"#]
+impl Parser {
+  #[rule = "Stmts -> Stmt Stmts"]
+  fn stmts(s: String, mut ss: Vec<String>) -> Vec<String> { (ss.insert(0, s), ss).1 }
+
+  #[rule = "Stmts ->"]
+  fn stmts0() -> Vec<String> { vec![] }
+
+  #[rule = "Stmt -> Id Colon Stmt"]
+  fn label(l: Token, _: Token, s: String) -> String { format!("{}: {}", std::str::from_utf8(l.piece).unwrap(), s) }
+
+  #[rule = "Stmt -> Id Assign IntLit Semi"]
+  fn assign(l: Token, _: Token, r: Token, _: Token) -> String {
+    format!("{} = {}", std::str::from_utf8(l.piece).unwrap(), std::str::from_utf8(r.piece).unwrap())
+  }
+}
+
+#[test]
+fn ll_k() {
+  assert_eq!(Parser.parse(&mut Lexer::new(b"a = 1; b: c: d = 2; e = 3;")), Ok(vec!["a = 1".to_owned(), "b: c: d = 2".to_owned(), "e = 3".to_owned()]));
+  assert!(Parser.parse(&mut Lexer::new(b"a: b = 1")).is_err());
+}
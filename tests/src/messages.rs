@@ -0,0 +1,73 @@
+use parser_macros::lalr1;
+
+struct Parser;
+
+#[lalr1(Expr)]
+#[lex = r#"
+priority = [{ assoc = 'left', terms = ['Add'] }]
+
+[lexical]
+'\(' = 'LPar'
+'\)' = 'RPar'
+'\+' = 'Add'
+'\d+' = 'IntLit'
+'\s+' = '_Eps'
+"#]
+#[messages = "tests/src/expr.messages"]
+impl Parser {
+  #[rule = "Expr -> Expr Add Expr"]
+  fn expr_add(l: i32, _op: Token, r: i32) -> i32 { l + r }
+  #[rule = "Expr -> LPar Expr RPar"]
+  fn expr_paren(_l: Token, i: i32, _r: Token) -> i32 { i }
+  #[rule = "Expr -> IntLit"]
+  fn expr_int(i: Token) -> i32 { std::str::from_utf8(i.piece).unwrap().parse().unwrap() }
+}
+
+#[test]
+fn messages() {
+  let error = |s| Parser.parse(&mut Lexer::new(s)).unwrap_err().to_string();
+  assert_eq!(error(b"1 + )"), "1:5: expected an expression after `+`");
+  assert_eq!(error(b"(1 + (2 3"), "1:9: unmatched `(`, or missing operator");
+  // no message for this state
+  assert_eq!(error(b")"), r#"1:1: unexpected RPar ")", expected LPar or IntLit"#);
+}
+
+#[test]
+fn list_errors() {
+  use common::*;
+  use lalr1_core::{lr0, lalr1_by_lr0, mk_table};
+  use parser_gen::messages::{self, error_state};
+  use std::collections::BTreeSet;
+  // "E -> c." is shared after `a` and `b`, and the state "S -> b E.y" is only reached by reducing it after `b`
+  let mut raw = parse_lines("S -> a E x\nS -> b E y\nE -> c\n").unwrap();
+  let ref g = raw.extend(false).unwrap();
+  let mut table = mk_table::mk_table(&lalr1_by_lr0::work(lr0::work(g), g), g);
+  mk_table::solve(&mut table, g);
+  let id = |name: &str| g.terms.iter().position(|t| t.name == name).unwrap() as u32;
+  let samples = messages::samples(g, &table);
+  for (state, sentence) in &samples { assert_eq!(error_state(g, &table, sentence), Some(*state)); }
+  let (b_c_x, a_c_y) = (vec![id("b"), id("c"), id("x")], vec![id("a"), id("c"), id("y")]);
+  assert!(samples.iter().any(|x| x.1 == b_c_x));
+
+  // every state where an error can be detected by a sentence of at most 4 tokens is listed
+  let terms = (EOF_IDX as u32..g.terms.len() as u32).filter(|&t| t != ERR_IDX as u32).collect::<Vec<_>>();
+  let mut all = BTreeSet::new();
+  let mut q = vec![Vec::new()];
+  while let Some(s) = q.pop() {
+    for &t in &terms {
+      let mut s = s.clone();
+      s.push(t);
+      if let Some(state) = error_state(g, &table, &s) { all.insert(state); }
+      if s.len() < 4 && t != EOF_IDX as u32 { q.push(s); }
+    }
+  }
+  assert_eq!(samples.iter().map(|x| x.0).collect::<BTreeSet<_>>(), all);
+
+  // the state of "S -> a E.x" has a message, the others are listed
+  let m = messages::compile(g, &table, "# a c y\na c y\n\nexpected `x`\n").unwrap();
+  let (covered, listed) = (error_state(g, &table, &a_c_y).unwrap(), error_state(g, &table, &b_c_x).unwrap());
+  let out = messages::uncovered(g, &table, Some(&m)).to_string();
+  assert!(!out.contains(&format!("# state {}\n", covered)));
+  assert!(out.contains(&format!("# state {}\nb c x\n\n<YOUR SYNTAX ERROR MESSAGE HERE>\n\n", listed)));
+  assert_eq!(out.matches("# state").count(), samples.len() - 1);
+}
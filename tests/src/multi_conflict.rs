@@ -0,0 +1,42 @@
+use parser_macros::lalr1;
+
+struct Parser;
+
+// after the first `Id`, faced with another `Id`, it can shift, reduce `A -> Id` or reduce `B -> Id`
+// `A -> Id` has higher precedence than both `Id` and `B -> Id`, so it wins
+#[lalr1(S)]
+#[lex = r#"
+priority = [
+  { assoc = 'left', terms = ['Lo'] },
+  { assoc = 'left', terms = ['Id'] },
+  { assoc = 'left', terms = ['Hi'] },
+]
+
+[lexical]
+'<' = 'Lo'
+'>' = 'Hi'
+'[a-z]+' = 'Id'
+'\s+' = '_Eps'
+"#]
+impl Parser {
+  #[rule = "S -> A Id"]
+  fn s_a(a: String, i: Token) -> String { format!("{} {}", a, std::str::from_utf8(i.piece).unwrap()) }
+  #[rule = "S -> B Id"]
+  fn s_b(b: String, i: Token) -> String { format!("{} {}", b, std::str::from_utf8(i.piece).unwrap()) }
+  #[rule = "S -> Id Id Id"]
+  fn s_id(_a: Token, _b: Token, _c: Token) -> String { "Id Id Id".to_owned() }
+  #[rule = "S -> Lo Hi"]
+  fn s_lo_hi(_l: Token, _h: Token) -> String { "Lo Hi".to_owned() }
+  #[rule = "A -> Id"]
+  #[prec = "Hi"]
+  fn a(_i: Token) -> String { "A".to_owned() }
+  #[rule = "B -> Id"]
+  #[prec = "Lo"]
+  fn b(_i: Token) -> String { "B".to_owned() }
+}
+
+#[test]
+fn multi_conflict() {
+  assert_eq!(Parser.parse(&mut Lexer::new(b"x y")).ok(), Some("A y".to_owned()));
+  assert!(Parser.parse(&mut Lexer::new(b"x y z")).is_err());
+}
@@ -0,0 +1,71 @@
+use parser_macros::ll1;
+use common::{HashSet, HashMap};
+
+struct Parser;
+
+// `Expr` is left recursive, but it is parsed by precedence climbing, so it can be used in ll1 mode
+#[ll1(Expr)]
+#[lex = r#"
+priority = [
+  { assoc = 'no_assoc', terms = ['Lt'] },
+  { assoc = 'left', terms = ['Add', 'Sub'] },
+  { assoc = 'left', terms = ['Mul'] },
+  { assoc = 'right', terms = ['Pow'] },
+  { assoc = 'no_assoc', terms = ['UMinus'] },
+  { assoc = 'left', terms = ['Bang'] },
+]
+pratt = ['Expr']
+
+[lexical]
+'<' = 'Lt'
+'\+' = 'Add'
+'-' = 'Sub'
+'\*\*' = 'Pow'
+'\*' = 'Mul'
+'!' = 'Bang'
+'\(' = 'LPar'
+'\)' = 'RPar'
+'\d+' = 'IntLit'
+'\s+' = '_Eps'
+"#]
+impl Parser {
+  #[rule = "Expr -> Expr Lt Expr"]
+  fn lt(l: i32, _: Token, r: i32) -> i32 { (l < r) as i32 }
+
+  #[rule = "Expr -> Expr Add Expr"]
+  fn add(l: i32, _: Token, r: i32) -> i32 { l + r }
+
+  #[rule = "Expr -> Expr Sub Expr"]
+  fn sub(l: i32, _: Token, r: i32) -> i32 { l - r }
+
+  #[rule = "Expr -> Expr Mul Expr"]
+  fn mul(l: i32, _: Token, r: i32) -> i32 { l * r }
+
+  #[rule = "Expr -> Expr Pow Expr"]
+  fn pow(l: i32, _: Token, r: i32) -> i32 { l.pow(r as u32) }
+
+  #[rule = "Expr -> Sub Expr"]
+  #[prec = "UMinus"]
+  fn neg(_: Token, r: i32) -> i32 { -r }
+
+  #[rule = "Expr -> Expr Bang"]
+  fn fact(l: i32, _: Token) -> i32 { (1..=l).product() }
+
+  #[rule = "Expr -> LPar Expr RPar"]
+  fn paren(_: Token, x: i32, _: Token) -> i32 { x }
+
+  #[rule = "Expr -> IntLit"]
+  fn int(i: Token) -> i32 { std::str::from_utf8(i.piece).unwrap().parse().unwrap() }
+}
+
+#[test]
+fn pratt() {
+  let parse = |s: &str| Parser.parse(&mut Lexer::new(s.as_bytes())).map_err(|e| e.len());
+  assert_eq!(parse("1 - 2 - 3 * 4 + 5"), Ok(-8));
+  assert_eq!(parse("2 ** 3 ** 2"), Ok(512));
+  // `UMinus` has higher priority than `Pow`, so it is (-2) ** 2
+  assert_eq!(parse("-2 * 3! - -(1 + 1) ** 2"), Ok(-16));
+  assert_eq!(parse("1 + 2 < 4"), Ok(1));
+  assert!(parse("1 < 2 < 3").is_err());
+  assert!(parse("1 + * 2").is_err());
+}
@@ -0,0 +1,36 @@
+use parser_macros::lalr1;
+
+struct Parser;
+
+#[lalr1(Expr)]
+#[lex = r#"
+priority = [{ assoc = 'left', terms = ['Add'] }]
+
+[lexical]
+'\+' = 'Add'
+'\d+' = 'IntLit'
+'\s+' = '_Eps'
+"#]
+impl Parser {
+  #[rule = "Expr -> Expr Add Expr"]
+  fn expr_add(l: i32, _op: Token, r: i32) -> i32 { l + r }
+  #[rule = "Expr -> IntLit"]
+  fn expr_int(i: Token) -> i32 { std::str::from_utf8(i.piece).unwrap().parse().unwrap() }
+}
+
+#[test]
+fn push() {
+  let token = |kind, piece: &'static str| Token { kind, piece: piece.as_bytes(), line: 1, col: 1 };
+  let mut parser = Parser;
+  let mut p = parser.push_parser();
+  for (kind, piece) in [(TokenKind::IntLit, "1"), (TokenKind::Add, "+"), (TokenKind::IntLit, "20")] {
+    assert!(matches!(p.push(token(kind, piece)), Status::NeedMore));
+  }
+  assert!(matches!(p.push(token(TokenKind::_Eof, "")), Status::Accepted(21)));
+  let mut p = parser.push_parser();
+  assert!(matches!(p.push(token(TokenKind::IntLit, "1")), Status::NeedMore));
+  match p.push(token(TokenKind::IntLit, "2")) {
+    Status::Error(e) => assert_eq!(e.expected, [TokenKind::_Eof, TokenKind::Add]),
+    _ => panic!("expect an error"),
+  }
+}
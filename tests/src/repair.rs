@@ -0,0 +1,40 @@
+use parser_macros::lalr1;
+
+struct Parser;
+
+#[lalr1(Stmts)]
+#[repair]
+#[lex = r#"
+priority = [{ assoc = 'left', terms = ['Add'] }]
+
+[lexical]
+';' = 'Semi'
+'\(' = 'LPar'
+'\)' = 'RPar'
+'\+' = 'Add'
+'\d+' = 'IntLit'
+'\s+' = '_Eps'
+"#]
+impl Parser {
+  #[rule = "Stmts -> Stmts Expr Semi"]
+  fn stmts(mut l: Vec<i32>, r: i32, _: Token) -> Vec<i32> { (l.push(r), l).1 }
+  #[rule = "Stmts ->"]
+  fn stmts0() -> Vec<i32> { vec![] }
+  #[rule = "Expr -> Expr Add Expr"]
+  fn expr_add(l: i32, _op: Token, r: i32) -> i32 { l + r }
+  #[rule = "Expr -> LPar Expr RPar"]
+  fn expr_paren(_l: Token, i: i32, _r: Token) -> i32 { i }
+  // an inserted token has an empty piece
+  #[rule = "Expr -> IntLit"]
+  fn expr_int(i: Token) -> i32 { std::str::from_utf8(i.piece).unwrap().parse().unwrap_or(0) }
+}
+
+#[test]
+fn repair() {
+  // (value, the columns of the errors, repairs)
+  let parse = |s| Parser.parse(&mut Lexer::new(s)).map(|(res, errors, repairs)|
+    (res, errors.iter().map(|e| e.token.col).collect::<Vec<_>>(), repairs.iter().map(|r| r.to_string()).collect::<Vec<_>>())).map_err(|e| e.token.kind);
+  assert_eq!(parse(b"1 + 2; 3 4;"), Ok((vec![3, 3], vec![10], vec![r#"1:10: deleted IntLit "4""#.to_owned()])));
+  assert_eq!(parse(b"(1 + 2; 1 + ;"), Ok((vec![3, 1], vec![7, 13], vec!["1:7: inserted RPar".to_owned(), "1:13: inserted IntLit".to_owned()])));
+  assert_eq!(parse(b"1 ) ) ) ) 2;"), Err(TokenKind::RPar));
+}
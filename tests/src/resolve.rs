@@ -0,0 +1,70 @@
+use parser_macros::lalr1;
+
+struct Parser;
+
+// `Cond -> Id` is ambiguous, it is a variable when followed by `Id`, and a literal when followed by `If`
+// without the directives, it would always be a literal, because `Lit -> Id` comes first
+#[lalr1(Stmt)]
+#[lex = r#"
+priority = []
+resolve = [
+  { token = 'Else', lhs = 'Stmt', prefer = 'shift' },
+  { token = 'Id', prefer = 'Var -> Id', over = 'Lit -> Id' },
+  { token = 'If', prefer = 'Lit -> Id' },
+]
+
+[lexical]
+'if' = 'If'
+'else' = 'Else'
+'[a-z]+' = 'Id'
+'\s+' = '_Eps'
+"#]
+impl Parser {
+  #[rule = "Stmt -> If Cond Stmt"]
+  fn stmt_if(_i: Token, c: String, s: String) -> String { format!("if({},{})", c, s) }
+  #[rule = "Stmt -> If Cond Stmt Else Stmt"]
+  fn stmt_if_else(_i: Token, c: String, s: String, _e: Token, e: String) -> String { format!("if({},{},{})", c, s, e) }
+  #[rule = "Stmt -> Id"]
+  fn stmt_id(i: Token) -> String { std::str::from_utf8(i.piece).unwrap().to_owned() }
+  #[rule = "Lit -> Id"]
+  fn lit(i: Token) -> String { format!("lit {}", std::str::from_utf8(i.piece).unwrap()) }
+  #[rule = "Var -> Id"]
+  fn var(i: Token) -> String { format!("var {}", std::str::from_utf8(i.piece).unwrap()) }
+  #[rule = "Cond -> Lit"]
+  fn cond_lit(l: String) -> String { l }
+  #[rule = "Cond -> Var"]
+  fn cond_var(v: String) -> String { v }
+}
+
+#[test]
+fn resolve() {
+  let parse = |s: &[u8]| Parser.parse(&mut Lexer::new(s)).ok().unwrap();
+  assert_eq!(parse(b"if a b"), "if(var a,b)");
+  assert_eq!(parse(b"if a if b c"), "if(lit a,if(var b,c))");
+  assert_eq!(parse(b"if a if b c else d"), "if(lit a,if(var b,c,d))");
+}
+
+// the unresolved conflicts of the grammar with the directives `resolve`, the terminal `error` is the reserved one
+fn conflicts(src: &'static str, resolve: Vec<common::RawResolve<'static>>) -> Vec<String> {
+  let mut raw = common::parse_lines(src).unwrap();
+  raw.lexical.retain(|_, &mut t| t != common::ERROR);
+  raw.resolve = resolve;
+  let ref g = raw.extend(false).unwrap();
+  let lalr1 = lalr1_core::lalr1_by_lr0::work(lalr1_core::lr0::work(g), g);
+  let mut table = lalr1_core::mk_table::mk_table(&lalr1, g);
+  parser_gen::show_lr::conflict(g, &lalr1_core::mk_table::solve(&mut table, g).0)
+}
+
+#[test]
+fn resolve_prod() {
+  use common::RawResolve;
+  // productions containing `error` can be named
+  const ERR: &str = "S -> A\nS -> B\nA -> error\nB -> error";
+  assert_eq!(conflicts(ERR, vec![]).len(), 1);
+  assert!(conflicts(ERR, vec![RawResolve { token: "_Eof", lhs: None, prefer: "B -> error", over: None }]).is_empty());
+
+  // a production in `prefer` doesn't solve shift-reduce conflicts, `reduce` does
+  const SR: &str = "S -> Id Id\nS -> A Id Id\nA -> Id";
+  assert_eq!(conflicts(SR, vec![RawResolve { token: "Id", lhs: None, prefer: "A -> Id", over: None }]).len(), 1);
+  assert!(conflicts(SR, vec![RawResolve { token: "Id", lhs: Some("A"), prefer: "reduce", over: None }]).is_empty());
+}
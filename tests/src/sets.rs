@@ -0,0 +1,26 @@
+use common::*;
+use ll1_core::*;
+
+const GRAMMAR: &str = "S -> A x
+A -> B C
+B -> b
+B ->
+C -> c
+";
+
+#[test]
+fn sets() {
+  let mut raw = parse_lines(GRAMMAR).unwrap();
+  let ref g = raw.extend(false).unwrap();
+  let id = |name: &str| g.terms.iter().position(|t| t.name == name).map(|x| x as u32)
+    .unwrap_or_else(|| (g.terms.len() + g.nt.iter().position(|t| t.name == name).unwrap()) as u32);
+  let nt = |name: &str| id(name) as usize - g.terms.len();
+  let ll = LLCtx::new(g);
+  let nullable = Nullable::new(g);
+  assert!(nullable.get(nt("B")) && !nullable.get(nt("A")));
+  assert!(nullable.of(&[id("B"), id("B")], g) && !nullable.of(&[id("B"), id("x")], g));
+  assert_eq!(sets::names(g, &ll.first.terms(nt("A"))), ["b", "c"]);
+  assert_eq!(sets::names(g, &ll.first.terms_of(&[id("B"), id("x")], g)), ["x", "b"]);
+  assert_eq!(sets::names(g, &ll.follow.terms(nt("B"))), ["c"]);
+  assert!(sets::dump_text(g, &ll.first, &ll.follow).to_string().starts_with("S:\n  nullable: false\n  first: b c\n  follow: _Eof\n"));
+}
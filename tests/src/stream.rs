@@ -0,0 +1,78 @@
+use parser_macros::lalr1;
+
+struct Parser;
+
+#[lalr1(Expr)]
+#[stream]
+#[lex = r#"
+priority = [{ assoc = 'left', terms = ['Add'] }]
+
+[lexical]
+'\+' = 'Add'
+'\d+' = 'IntLit'
+'\s+' = '_Eps'
+"#]
+impl Parser {
+  #[rule = "Expr -> Expr Add Expr"]
+  fn expr_add(l: i32, _op: Token, r: i32) -> i32 { l + r }
+  #[rule = "Expr -> IntLit"]
+  fn expr_int(i: Token) -> i32 { std::str::from_utf8(i.piece).unwrap().parse().unwrap() }
+}
+
+// returns at most 3 bytes for each `read`, so tokens are split across reads
+struct Chunked<'a>(&'a [u8]);
+
+impl std::io::Read for Chunked<'_> {
+  fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+    let n = buf.len().min(self.0.len()).min(3);
+    buf[..n].copy_from_slice(&self.0[..n]);
+    self.0 = &self.0[n..];
+    Ok(n)
+  }
+}
+
+// generates "1 + 1 + ... + 1" of `n` terms on the fly, `pos` is the number of bytes already returned
+struct Ones { n: usize, pos: usize }
+
+impl std::io::Read for Ones {
+  fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+    let n = buf.len().min(self.n * 4 - 3 - self.pos);
+    for (i, b) in buf[..n].iter_mut().enumerate() { *b = b"1 + "[(self.pos + i) % 4]; }
+    self.pos += n;
+    Ok(n)
+  }
+}
+
+#[test]
+fn stream() {
+  let input = b"12345 + 6\n  + 789 +1";
+  let (mut lexer, mut stream) = (Lexer::new(input), StreamLexer::new(Chunked(input)));
+  loop {
+    let (t, s) = (lexer.next(), stream.next().unwrap());
+    assert_eq!(t, s.as_token());
+    if t.kind == TokenKind::_Eof { break; }
+  }
+  // tokens own their pieces, so they can be collected and fed to the push parser
+  let mut stream = StreamLexer::new(Chunked(input));
+  let mut tokens = Vec::new();
+  loop {
+    let t = stream.next().unwrap();
+    tokens.push(t.clone());
+    if t.kind == TokenKind::_Eof { break; }
+  }
+  let mut parser = Parser;
+  let mut p = parser.push_parser();
+  let res = tokens.iter().map(|t| p.push(t.as_token())).last();
+  assert!(matches!(res, Some(Status::Accepted(13141))));
+  assert_eq!(Parser.parse_stream(&mut StreamLexer::new(Chunked(input))).unwrap(), 13141);
+  let e = Parser.parse_stream(&mut StreamLexer::new(Chunked(b"1 + + 2"))).unwrap_err();
+  assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+  assert_eq!(e.to_string(), r#"1:5: unexpected Add "+", expected IntLit"#);
+}
+
+#[test]
+fn parse_stream() {
+  // the 4MB input only exists chunk by chunk, and `Add` is left associative, so the parser stack stays shallow
+  let n = 1 << 20;
+  assert_eq!(Parser.parse_stream(&mut StreamLexer::new(Ones { n, pos: 0 })).unwrap(), n as i32);
+}